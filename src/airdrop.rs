@@ -0,0 +1,32 @@
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+/// Hoja del árbol de Merkle de un airdrop
+///
+/// sha256 del XDR de la tupla (index, account, amount). `index` es la
+/// posición de la hoja y se usa para el bitmap de claims
+pub fn leaf_hash(env: &Env, index: u32, account: &Address, amount: i128) -> BytesN<32> {
+    let leaf = (index, account.clone(), amount);
+    env.crypto().sha256(&leaf.to_xdr(env)).into()
+}
+
+/// Verifica que `leaf` pertenece al árbol con raíz `root`
+///
+/// Cada nodo es sha256(a || b) con el par ordenado (a <= b), así la
+/// prueba no necesita indicar si cada hermano va a izquierda o derecha
+pub fn verify_proof(env: &Env, leaf: &BytesN<32>, proof: &Vec<BytesN<32>>, root: &BytesN<32>) -> bool {
+    let mut computed = leaf.to_array();
+    for sibling in proof.iter() {
+        let sibling = sibling.to_array();
+        let (a, b) = if computed <= sibling {
+            (computed, sibling)
+        } else {
+            (sibling, computed)
+        };
+
+        let mut pair = Bytes::from_array(env, &a);
+        pair.extend_from_array(&b);
+        computed = env.crypto().sha256(&pair).to_array();
+    }
+
+    computed == root.to_array()
+}
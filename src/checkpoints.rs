@@ -0,0 +1,94 @@
+use soroban_sdk::Env;
+
+use crate::storage::{Checkpoint, DataKey, History};
+
+/// Registra `value` en el historial como valor vigente desde el ledger actual
+///
+/// El valor más reciente vive en `head_key` (ver History); al cambiar
+/// en un ledger posterior, el anterior pasa a la entrada `entry(count)`.
+/// Así una cuenta nueva solo agrega una key y varios cambios en el
+/// mismo ledger sobrescriben el head.
+///
+/// `previous` es el valor antes de este cambio. Si no hay historial y
+/// no es 0 (un balance anterior al historial, de antes de un upgrade),
+/// queda como entrada con ledger 0: vigente desde antes del historial
+pub fn push(env: &Env, head_key: &DataKey, entry: impl Fn(u32) -> DataKey, previous: i128, value: i128) {
+    let storage = env.storage().persistent();
+    let ledger = env.ledger().sequence();
+
+    let head = match storage.get::<_, History>(head_key) {
+        Some(head) if head.ledger == ledger => History { value, ..head },
+        Some(head) => {
+            write_checkpoint(env, &entry(head.count), head.ledger, head.value);
+            History { count: head.count + 1, ledger, value }
+        }
+        None if previous != 0 => {
+            write_checkpoint(env, &entry(0), 0, previous);
+            History { count: 1, ledger, value }
+        }
+        None => History { count: 0, ledger, value },
+    };
+
+    storage.set(head_key, &head);
+    storage.extend_ttl(head_key, 100_000, 200_000);
+}
+
+/// Valor más reciente del historial (0 si está vacío)
+pub fn latest(env: &Env, head_key: &DataKey) -> i128 {
+    env.storage().persistent()
+        .get::<_, History>(head_key)
+        .map_or(0, |head| head.value)
+}
+
+/// Valor vigente al final de `ledger` según el historial
+///
+/// Búsqueda binaria de la última entrada con ledger <= `ledger`;
+/// 0 si no hay ninguna. Sin historial devuelve `current`: el valor
+/// no cambió desde que existe el historial (ver push).
+///
+/// Las entradas que lee quedan con TTL extendido: un historial que
+/// se consulta no se archiva. Una entrada que nadie leyó ni escribió
+/// en ~200.000 ledgers sí, y hay que restaurarla antes de consultarla
+pub fn lookup(env: &Env, head_key: &DataKey, entry: impl Fn(u32) -> DataKey, ledger: u32, current: i128) -> i128 {
+    let storage = env.storage().persistent();
+    let Some(head) = storage.get::<_, History>(head_key) else {
+        return current;
+    };
+    storage.extend_ttl(head_key, 100_000, 200_000);
+    if head.ledger <= ledger {
+        return head.value;
+    }
+
+    let mut low = 0;
+    let mut high = head.count;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if read_checkpoint(env, &entry(mid)).ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    match low.checked_sub(1) {
+        Some(index) => read_checkpoint(env, &entry(index)).value,
+        None => 0,
+    }
+}
+
+/// Lee una entrada del historial y extiende su TTL
+///
+/// Toda entrada con índice < History::count existe (push nunca la borra)
+fn read_checkpoint(env: &Env, key: &DataKey) -> Checkpoint {
+    let storage = env.storage().persistent();
+    let checkpoint = storage.get(key).unwrap();
+    storage.extend_ttl(key, 100_000, 200_000);
+    checkpoint
+}
+
+/// Guarda una entrada del historial con TTL extendido
+fn write_checkpoint(env: &Env, key: &DataKey, ledger: u32, value: i128) {
+    let storage = env.storage().persistent();
+    storage.set(key, &Checkpoint { ledger, value });
+    storage.extend_ttl(key, 100_000, 200_000);
+}
@@ -43,4 +43,144 @@ pub enum TokenError {
     /// Nombre o símbolo inválido (vacío o muy largo)
     /// Validación de metadatos en initialize()
     InvalidMetadata = 9,
+    
+    /// expiration_ledger de un approve ya pasó o excede el TTL máximo
+    /// Solo se permite un ledger pasado al revocar (amount = 0)
+    InvalidExpiration = 10,
+    
+    /// No hay un admin propuesto pendiente
+    /// accept_admin() requiere propose_admin() previo
+    NoPendingAdmin = 11,
+    
+    /// El admin renunció al rol
+    /// Las funciones administrativas quedan deshabilitadas para siempre
+    AdminRenounced = 12,
+    
+    /// La cuenta no tiene el rol requerido
+    /// Ver Role en storage.rs
+    Unauthorized = 13,
+    
+    /// El minter no tiene cupo suficiente
+    /// Cupo total o límite de la ventana actual agotado
+    MintQuotaExceeded = 14,
+    
+    /// El mint superaría el tope de emisión (max_supply)
+    /// También al intentar subir un tope ya fijado
+    SupplyCapExceeded = 15,
+    
+    /// La operación está pausada por el circuit breaker
+    /// Ver pause()/unpause()
+    Paused = 16,
+    
+    /// La cuenta está congelada (set_authorized(id, false))
+    /// No puede enviar, recibir ni quemar tokens
+    AccountFrozen = 17,
+    
+    /// El token se inicializó sin clawback
+    /// clawback_enabled es inmutable después de initialize()
+    ClawbackDisabled = 18,
+    
+    /// Nonce de permit incorrecto
+    /// Debe ser igual a permit_nonce(owner); evita replays
+    InvalidNonce = 19,
+    
+    /// Lote sin partidas
+    /// batch_transfer y mint_batch requieren al menos un destinatario
+    EmptyBatch = 20,
+    
+    /// Lote con más partidas que max_batch_size()
+    /// Límite para no exceder los recursos de una invocación
+    BatchTooLarge = 21,
+    
+    /// No hay un airdrop en curso
+    /// Ver start_airdrop()
+    NoAirdrop = 22,
+    
+    /// Ya hay un airdrop en curso
+    /// sweep_airdrop() lo cierra después de su deadline
+    AirdropActive = 23,
+    
+    /// El plazo del airdrop terminó
+    /// Ya no se aceptan claims
+    AirdropExpired = 24,
+    
+    /// El airdrop todavía acepta claims
+    /// sweep_airdrop() solo después del deadline
+    AirdropNotEnded = 25,
+    
+    /// Este index del airdrop ya se reclamó
+    /// Cada hoja se paga una sola vez
+    AlreadyClaimed = 26,
+    
+    /// La prueba de Merkle no corresponde a la raíz del airdrop
+    /// (index, account, amount) no está en el árbol
+    InvalidProof = 27,
+    
+    /// No existe un calendario de vesting con ese id
+    /// Ver create_vesting()
+    VestingNotFound = 28,
+    
+    /// Fechas de vesting inválidas
+    /// Debe cumplirse start_ledger <= cliff_ledger <= end_ledger y start < end
+    InvalidSchedule = 29,
+    
+    /// No hay tokens liberados pendientes de pago
+    /// Antes del cliff o todo ya liberado
+    NothingToRelease = 30,
+    
+    /// El calendario no es revocable o ya fue revocado
+    /// revocable se fija en create_vesting()
+    NotRevocable = 31,
+    
+    /// Consulta histórica sobre el ledger actual o uno futuro
+    /// balance_at/total_supply_at/get_past_votes solo aceptan ledgers pasados
+    FutureLedger = 32,
+    
+    /// Política de comisión inválida
+    /// bps mayor que MAX_FEE_BPS o min_fee > max_fee
+    InvalidFeePolicy = 33,
+    
+    /// El destinatario no está en la allowlist
+    /// Solo en tokens inicializados con allowlist_enabled = true
+    NotAllowlisted = 34,
+    
+    /// La cuenta está en la denylist (sanciones)
+    /// No puede enviar, recibir, quemar ni aprobar
+    AccountDenylisted = 35,
+    
+    /// El storage usa un esquema anterior
+    /// El admin debe ejecutar migrate() antes de operar
+    SchemaOutdated = 36,
+    
+    /// El storage ya usa el esquema actual
+    /// migrate() no tiene nada que hacer
+    AlreadyMigrated = 37,
+    
+    /// La operación requiere pasar por el timelock
+    /// Usar schedule()/execute() mientras timelock_delay() > 0
+    TimelockRequired = 38,
+    
+    /// El eta es anterior al ledger actual + timelock_delay()
+    /// Los holders deben tener la ventana completa
+    TimelockTooEarly = 39,
+    
+    /// La operación programada todavía no llegó a su eta
+    /// Ver scheduled_op()
+    TimelockNotReady = 40,
+    
+    /// No existe una operación programada con ese id
+    /// Ya ejecutada, cancelada o nunca programada
+    OperationNotFound = 41,
+    
+    /// Faltan aprobaciones del admin multisig
+    /// Se necesitan al menos `threshold` firmantes en `approvers`
+    ThresholdNotMet = 42,
+    
+    /// Conjunto de firmantes inválido
+    /// Umbral en 1..=firmantes, sin duplicados y hasta MAX_ADMIN_SIGNERS
+    InvalidSignerSet = 43,
+    
+    /// La cuenta es la reserva del propio contrato
+    /// No se puede congelar, sancionar ni hacerle clawback
+    ReservedAccount = 44,
 }
\ No newline at end of file
@@ -1,14 +1,18 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, Address, Env, String, 
-    contractevent
+    contract, contractimpl, Address, BytesN, Env, MuxedAddress, String, TryFromVal, Val,
+    Vec, contractevent, panic_with_error, token::TokenInterface
 };
 
 mod storage;
 mod errors;
+mod permit;
+mod airdrop;
+mod vesting;
+mod checkpoints;
 
-use storage::DataKey;
+use storage::{AdminOp, Airdrop, AllowanceValue, DataKey, MinterQuota, FeePolicy, PauseScope, PauseState, Role, ScheduledOp, SignerSet, TokenConfig, TokenMetadata, VestingSchedule};
 use errors::TokenError;
 
 /// Constantes de configuración
@@ -16,6 +20,65 @@ const MAX_DECIMALS: u32 = 18;
 const MAX_NAME_LENGTH: u32 = 100;
 const MAX_SYMBOL_LENGTH: u32 = 32;
 
+/// Entradas de ledger que una transacción puede escribir (límite de la red)
+const MAX_WRITE_ENTRIES: u32 = 50;
+
+/// Entradas escritas por partida de un lote en el peor caso
+///
+/// Balance, BalanceHistory y BalanceCheckpoint del destinatario más
+/// VotesHistory y VotesCheckpoint de su delegado
+const BATCH_WRITE_ENTRIES_PER_LEG: u32 = 5;
+
+/// Entradas escritas por un lote además de sus partidas
+///
+/// batch_transfer: el sender y la tesorería con sus delegados (2 x 5)
+/// y el nonce de la firma; mint_batch: instance, SupplyHistory,
+/// SupplyCheckpoint, el cupo del minter y el nonce
+const BATCH_BASE_WRITE_ENTRIES: u32 = 11;
+
+/// Tamaño máximo de lote (batch_transfer, mint_batch, migrate)
+///
+/// Un lote de este tamaño escribe como mucho MAX_WRITE_ENTRIES
+/// entradas aunque cada partida caiga en el peor caso
+const MAX_BATCH_SIZE: u32 = (MAX_WRITE_ENTRIES - BATCH_BASE_WRITE_ENTRIES) / BATCH_WRITE_ENTRIES_PER_LEG;
+
+/// Tamaño de lote por defecto; el admin puede reducirlo con set_max_batch_size()
+const DEFAULT_MAX_BATCH_SIZE: u32 = MAX_BATCH_SIZE;
+
+/// Versión del esquema de storage que entiende este código
+///
+/// - 1: esquema original (allowances guardados como i128, sin expiración)
+/// - 2: allowances como AllowanceValue (amount + expiration_ledger)
+///
+/// Un contrato con un esquema anterior debe ejecutar migrate()
+const STORAGE_VERSION: u32 = 2;
+
+/// Delay máximo del timelock: ~31 días de ledgers de 5 segundos
+///
+/// Evita que un delay enorme deje el token sin poder administrarse
+const MAX_TIMELOCK_DELAY: u32 = 535_680;
+
+/// Ledgers que una operación programada sigue disponible después de su eta
+///
+/// El TTL de DataKey::TimelockOp cubre el delay completo más esta
+/// ventana, así la entrada no se archiva antes de poder ejecutarla
+const TIMELOCK_EXECUTION_WINDOW: u32 = 100_000;
+
+/// Máximo de firmantes de un admin multisig
+///
+/// Cada aprobación es un require_auth() más en la invocación
+const MAX_ADMIN_SIGNERS: u32 = 20;
+
+/// Techo de la comisión por transferencia: 5% (en basis points)
+///
+/// set_fee_policy() no puede fijar una comisión mayor
+const MAX_FEE_BPS: u32 = 500;
+
+/// Máximo de cuentas exentas de comisión
+///
+/// La lista vive en instance storage y se recorre en cada transfer
+const MAX_FEE_EXEMPT: u32 = 20;
+
 #[contractevent]
 pub struct InitEvent {
     // [COM: El nombre de la struct 'InitEvent' es Topic[0]]
@@ -27,591 +90,3254 @@ pub struct InitEvent {
 }
 
 #[contractevent]
+pub struct MetadataUpdatedEvent {
+    #[topic]
+    pub caller: Address,        // [COM: Topic[1]]
+    pub name: String,           // [COM: Dato]
+    pub symbol: String,         // [COM: Dato]
+}
+
+#[contractevent]
+pub struct AdminProposedEvent {
+    #[topic]
+    pub admin: Address,         // [COM: Topic[1]]
+    pub pending_admin: Address, // [COM: Dato]
+}
+
+#[contractevent]
+pub struct AdminChangedEvent {
+    #[topic]
+    pub old_admin: Address,     // [COM: Topic[1]]
+    pub new_admin: Address,     // [COM: Dato]
+}
+
+#[contractevent]
+pub struct AdminRenouncedEvent {
+    #[topic]
+    pub admin: Address,         // [COM: Topic[1]]
+}
+
+#[contractevent]
+pub struct AdminSignersSetEvent {
+    #[topic]
+    pub admin: Address,         // [COM: Topic[1]]
+    pub signers: Vec<Address>,  // [COM: Dato]
+    pub threshold: u32,         // [COM: Dato]
+}
+
+#[contractevent]
+pub struct RoleGrantedEvent {
+    #[topic]
+    pub role: Role,             // [COM: Topic[1]]
+    #[topic]
+    pub account: Address,       // [COM: Topic[2]]
+    pub caller: Address,        // [COM: Dato]
+}
+
+#[contractevent]
+pub struct RoleRevokedEvent {
+    #[topic]
+    pub role: Role,             // [COM: Topic[1]]
+    #[topic]
+    pub account: Address,       // [COM: Topic[2]]
+    pub caller: Address,        // [COM: Dato] (igual a account en renounce_role)
+}
+
+#[contractevent]
+pub struct MinterQuotaSetEvent {
+    #[topic]
+    pub minter: Address,        // [COM: Topic[1]]
+    pub total: i128,            // [COM: Dato]
+    pub window_ledgers: u32,    // [COM: Dato]
+    pub window_limit: i128,     // [COM: Dato]
+}
+
+#[contractevent]
+pub struct MinterQuotaExhaustedEvent {
+    #[topic]
+    pub minter: Address,        // [COM: Topic[1]]
+}
+
+#[contractevent]
+pub struct MinterWindowExhaustedEvent {
+    #[topic]
+    pub minter: Address,        // [COM: Topic[1]]
+    pub window_end: u32,        // [COM: Dato] (primer ledger de la próxima ventana)
+}
+
+#[contractevent]
+pub struct AirdropStartedEvent {
+    #[topic]
+    pub admin: Address,         // [COM: Topic[1]]
+    pub id: u32,                // [COM: Dato]
+    pub merkle_root: BytesN<32>, // [COM: Dato]
+    pub amount: i128,           // [COM: Dato]
+    pub deadline_ledger: u32,   // [COM: Dato]
+}
+
+#[contractevent]
+pub struct AirdropClaimedEvent {
+    #[topic]
+    pub account: Address,       // [COM: Topic[1]]
+    pub id: u32,                // [COM: Dato]
+    pub index: u32,             // [COM: Dato]
+    pub amount: i128,           // [COM: Dato]
+}
+
+#[contractevent]
+pub struct AirdropSweptEvent {
+    #[topic]
+    pub admin: Address,         // [COM: Topic[1]]
+    pub id: u32,                // [COM: Dato]
+    pub amount: i128,           // [COM: Dato]
+}
+
+#[contractevent]
+pub struct VestingCreatedEvent {
+    #[topic]
+    pub beneficiary: Address,   // [COM: Topic[1]]
+    pub id: u32,                // [COM: Dato]
+    pub total: i128,            // [COM: Dato]
+    pub start_ledger: u32,      // [COM: Dato]
+    pub cliff_ledger: u32,      // [COM: Dato]
+    pub end_ledger: u32,        // [COM: Dato]
+    pub revocable: bool,        // [COM: Dato]
+}
+
+#[contractevent]
+pub struct VestingReleasedEvent {
+    #[topic]
+    pub beneficiary: Address,   // [COM: Topic[1]]
+    pub id: u32,                // [COM: Dato]
+    pub amount: i128,           // [COM: Dato]
+}
+
+#[contractevent]
+pub struct VestingRevokedEvent {
+    #[topic]
+    pub beneficiary: Address,   // [COM: Topic[1]]
+    pub id: u32,                // [COM: Dato]
+    pub unvested: i128,         // [COM: Dato]
+}
+
+#[contractevent]
+pub struct DelegateChangedEvent {
+    #[topic]
+    pub delegator: Address,             // [COM: Topic[1]]
+    pub from_delegate: Option<Address>, // [COM: Dato]
+    pub to_delegate: Address,           // [COM: Dato]
+}
+
+#[contractevent]
+pub struct DelegateVotesChangedEvent {
+    #[topic]
+    pub delegate: Address,      // [COM: Topic[1]]
+    pub previous_votes: i128,   // [COM: Dato]
+    pub new_votes: i128,        // [COM: Dato]
+}
+
+#[contractevent]
+pub struct FeePolicySetEvent {
+    #[topic]
+    pub admin: Address,                 // [COM: Topic[1]]
+    pub policy: Option<FeePolicy>,      // [COM: Dato]
+}
+
+#[contractevent]
+pub struct FeeEvent {
+    #[topic]
+    pub from: Address,          // [COM: Topic[1]]
+    #[topic]
+    pub treasury: Address,      // [COM: Topic[2]]
+    pub amount: i128,           // [COM: Dato]
+}
+
+#[contractevent]
+pub struct AllowlistUpdatedEvent {
+    #[topic]
+    pub account: Address,       // [COM: Topic[1]]
+    pub allowed: bool,          // [COM: Dato]
+    pub caller: Address,        // [COM: Dato]
+}
+
+#[contractevent]
+pub struct DenylistUpdatedEvent {
+    #[topic]
+    pub account: Address,       // [COM: Topic[1]]
+    pub denied: bool,           // [COM: Dato]
+    pub caller: Address,        // [COM: Dato]
+}
+
+#[contractevent]
+pub struct UpgradeEvent {
+    #[topic]
+    pub admin: Address,             // [COM: Topic[1]]
+    pub new_wasm_hash: BytesN<32>,  // [COM: Dato]
+    pub version: u32,               // [COM: Dato]
+}
+
+#[contractevent]
+pub struct MigratedEvent {
+    #[topic]
+    pub admin: Address,         // [COM: Topic[1]]
+    pub storage_version: u32,   // [COM: Dato]
+}
+
+#[contractevent]
+pub struct TimelockDelaySetEvent {
+    #[topic]
+    pub admin: Address,         // [COM: Topic[1]]
+    pub delay_ledgers: u32,     // [COM: Dato]
+}
+
+#[contractevent]
+pub struct OperationScheduledEvent {
+    #[topic]
+    pub op_id: u32,             // [COM: Topic[1]]
+    pub op: AdminOp,            // [COM: Dato]
+    pub eta_ledger: u32,        // [COM: Dato]
+}
+
+#[contractevent]
+pub struct OperationExecutedEvent {
+    #[topic]
+    pub op_id: u32,             // [COM: Topic[1]]
+}
+
+#[contractevent]
+pub struct OperationCancelledEvent {
+    #[topic]
+    pub op_id: u32,             // [COM: Topic[1]]
+}
+
+#[contractevent]
+pub struct MaxBatchSizeSetEvent {
+    #[topic]
+    pub admin: Address,         // [COM: Topic[1]]
+    pub max_batch_size: u32,    // [COM: Dato]
+}
+
+#[contractevent]
+pub struct MaxSupplySetEvent {
+    #[topic]
+    pub admin: Address,         // [COM: Topic[1]]
+    pub max_supply: i128,       // [COM: Dato]
+}
+
+#[contractevent]
+pub struct PausedEvent {
+    #[topic]
+    pub caller: Address,        // [COM: Topic[1]]
+    pub scope: PauseScope,      // [COM: Dato]
+}
+
+#[contractevent]
+pub struct UnpausedEvent {
+    #[topic]
+    pub caller: Address,        // [COM: Topic[1]]
+    pub scope: PauseScope,      // [COM: Dato]
+}
+
+// [COM: Los eventos siguientes usan el formato SEP-41: el primer topic es el
+// nombre del evento ("mint", "burn", "transfer", "approve") en lugar del
+// nombre de la struct, para que indexadores y wallets los reconozcan]
+
+#[contractevent(topics = ["mint"], data_format = "single-value")]
 pub struct MintEvent {
-    // [COM: El nombre de la struct 'MintEvent' es Topic[0]]
     #[topic]
     pub to: Address,        // [COM: Topic[1]]
     pub amount: i128,       // [COM: Dato]
-    pub new_balance: i128,  // [COM: Dato]
-    pub new_total: i128,    // [COM: Dato]
 }
 
-#[contractevent]
+#[contractevent(topics = ["burn"], data_format = "single-value")]
 pub struct BurnEvent {
-    // [COM: El nombre de la struct 'BurnEvent' es Topic[0]]
     #[topic]
     pub from: Address,      // [COM: Topic[1]]
     pub amount: i128,       // [COM: Dato]
-    pub new_balance: i128,  // [COM: Dato]
-    pub new_total: i128,    // [COM: Dato]
 }
 
-#[contractevent]
+#[contractevent(topics = ["transfer"], data_format = "single-value")]
 pub struct TransferEvent {
-    // [COM: El nombre de la struct 'TransferEvent' es Topic[0]]
     #[topic]
     pub from: Address,          // [COM: Topic[1]]
     #[topic]
     pub to: Address,            // [COM: Topic[2]]
     pub amount: i128,           // [COM: Dato]
-    pub new_from_balance: i128, // [COM: Dato]
-    pub new_to_balance: i128,   // [COM: Dato]
 }
 
-#[contractevent]
+#[contractevent(topics = ["transfer"], data_format = "map")]
+pub struct TransferMuxedEvent {
+    // [COM: Variante de TransferEvent cuando `to` es una dirección muxed (M...)]
+    #[topic]
+    pub from: Address,              // [COM: Topic[1]]
+    #[topic]
+    pub to: Address,                // [COM: Topic[2]]
+    pub to_muxed_id: Option<u64>,   // [COM: Dato]
+    pub amount: i128,               // [COM: Dato]
+}
+
+#[contractevent(topics = ["set_authorized"], data_format = "single-value")]
+pub struct SetAuthorizedEvent {
+    // [COM: Mismo formato que el evento del Stellar Asset Contract]
+    #[topic]
+    pub id: Address,            // [COM: Topic[1]]
+    pub authorize: bool,        // [COM: Dato]
+}
+
+#[contractevent(topics = ["clawback"], data_format = "single-value")]
+pub struct ClawbackEvent {
+    // [COM: Mismo formato que el evento del Stellar Asset Contract]
+    #[topic]
+    pub from: Address,          // [COM: Topic[1]]
+    pub amount: i128,           // [COM: Dato]
+}
+
+#[contractevent(topics = ["approve"], data_format = "vec")]
 pub struct ApproveEvent {
-    // [COM: El nombre de la struct 'ApproveEvent' es Topic[0]]
     #[topic]
     pub from: Address,          // [COM: Topic[1]]
     #[topic]
     pub spender: Address,       // [COM: Topic[2]]
-    pub old_allowance: i128,    // [COM: Dato]
-    pub new_allowance: i128,    // [COM: Dato]
+    pub amount: i128,           // [COM: Dato[0]]
+    pub expiration_ledger: u32, // [COM: Dato[1]]
 }
 // [COM: Fin de la definición de structs de eventos]
 
-/// Trait que define la interfaz del token según CAP-46
-/// 
-/// Esta es la interfaz estándar de tokens fungibles en Stellar
-/// Compatible con wallets, DEXs, y el ecosistema completo
+/// Trait con las funciones administrativas del token
+///
+/// La interfaz estándar SEP-41 (`soroban_sdk::token::TokenInterface`)
+/// no define cómo se crean tokens ni quién los administra, así que
+/// esas funciones viven aquí
 pub trait TokenTrait {
     /// Inicializa el token con metadatos y admin
-    /// 
+    ///
     /// Puede ser llamado solo una vez. Configura:
     /// - Admin: cuenta con permisos para mintear
     /// - Name: nombre completo del token
     /// - Symbol: identificador corto (ej: BDB, USDC)
     /// - Decimals: precisión del token (7 para Stellar)
+    /// - Config: tope de emisión, clawback y allowlist (ver TokenConfig)
     fn initialize(
-        env: Env, 
-        admin: Address, 
-        name: String, 
+        env: Env,
+        admin: Address,
+        name: String,
         symbol: String,
-        decimals: u32
+        decimals: u32,
+        config: TokenConfig
     ) -> Result<(), TokenError>;
-    
-    /// Crea nuevos tokens (solo admin)
-    /// 
+
+    /// Crea nuevos tokens (rol Minter)
+    ///
     /// Aumenta el supply total y el balance del destinatario
-    /// Requiere autorización del admin
-    fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError>;
-    
-    /// Destruye tokens reduciendo el supply
-    /// 
-    /// Reduce el supply total y el balance del owner
-    /// Requiere autorización del owner
-    fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError>;
-    
-    /// Consulta el balance de una cuenta
-    /// 
-    /// Devuelve 0 si la cuenta nunca ha recibido tokens
-    fn balance(env: Env, account: Address) -> i128;
-    
-    /// Transfiere tokens entre cuentas
-    /// 
-    /// Requiere autorización de `from`
-    /// No permite transferencias a sí mismo
-    fn transfer(
-        env: Env, 
-        from: Address, 
-        to: Address, 
-        amount: i128
-    ) -> Result<(), TokenError>;
-    
-    /// Aprueba a otro usuario para gastar tokens
-    /// 
-    /// Permite que `spender` gaste hasta `amount` tokens
-    /// de la cuenta de `from`. Se puede revocar con amount=0
-    fn approve(
-        env: Env, 
-        from: Address, 
-        spender: Address, 
-        amount: i128
-    ) -> Result<(), TokenError>;
-    
-    /// Consulta el allowance entre dos cuentas
-    /// 
-    /// Devuelve cuánto puede gastar `spender` de los tokens de `from`
-    fn allowance(env: Env, from: Address, spender: Address) -> i128;
-    
-    /// Transfiere tokens en nombre de otro usuario
-    /// 
-    /// Requiere allowance previo mediante approve()
-    /// Reduce el allowance automáticamente
-    fn transfer_from(
-        env: Env, 
-        spender: Address, 
-        from: Address, 
-        to: Address, 
-        amount: i128
+    /// Requiere autorización de `minter`, que debe tener el rol
+    /// Minter (el admin lo tiene implícitamente)
+    fn mint(env: Env, minter: Address, to: Address, amount: i128) -> Result<(), TokenError>;
+
+    /// Propone un nuevo admin (paso 1 de 2)
+    ///
+    /// Requiere autorización del admin actual. El cambio solo se
+    /// aplica cuando `new_admin` llama a accept_admin(), así una
+    /// dirección equivocada no deja el token sin admin
+    fn propose_admin(env: Env, new_admin: Address) -> Result<(), TokenError>;
+
+    /// Acepta la propuesta de admin (paso 2 de 2)
+    ///
+    /// Requiere autorización del admin pendiente
+    fn accept_admin(env: Env) -> Result<(), TokenError>;
+
+    /// Renuncia al rol de admin de forma permanente
+    ///
+    /// Después de esto nadie puede mintear ni recuperar el admin,
+    /// aunque existan cuentas con el rol Minter
+    fn renounce_admin(env: Env) -> Result<(), TokenError>;
+
+    /// Fija o reduce el tope de emisión (solo admin)
+    ///
+    /// Si el token no tiene tope se puede fijar una vez; después
+    /// solo se puede bajar, nunca subir ni quedar por debajo del
+    /// supply actual
+    fn set_max_supply(env: Env, max_supply: i128) -> Result<(), TokenError>;
+
+    /// Reemplaza el código del contrato por el WASM `new_wasm_hash` (solo admin)
+    ///
+    /// El WASM debe estar subido a la red. El storage (balances,
+    /// allowances, configuración) se conserva y version() se incrementa
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError>;
+
+    /// Migra el storage de un esquema anterior al actual (solo admin)
+    ///
+    /// Reescribe en el formato actual los allowances (owner, spender)
+    /// de `allowances` (hasta max_batch_size() por llamada; los pares
+    /// se obtienen off-chain de los eventos approve). Con `complete`
+    /// = true marca el esquema como migrado. Mientras el esquema esté
+    /// desactualizado las demás operaciones devuelven SchemaOutdated
+    fn migrate(
+        env: Env,
+        allowances: Vec<(Address, Address)>,
+        complete: bool
     ) -> Result<(), TokenError>;
-    
+
+    /// Versión del esquema de storage (1 en contratos anteriores a StorageVersion)
+    fn storage_version(env: Env) -> u32;
+
+    /// Actualiza nombre y símbolo (rol MetadataManager)
+    ///
+    /// Mismas reglas que initialize(). Los decimales son inmutables:
+    /// `metadata.decimals` debe coincidir con decimals()
+    fn set_metadata(env: Env, caller: Address, metadata: TokenMetadata) -> Result<(), TokenError>;
+
+    /// Nombre, símbolo y decimales en una sola llamada
+    fn metadata(env: Env) -> TokenMetadata;
+
     // Métodos de consulta (getters)
-    fn name(env: Env) -> String;
-    fn symbol(env: Env) -> String;
-    fn decimals(env: Env) -> u32;
     fn total_supply(env: Env) -> i128;
+    fn max_supply(env: Env) -> Option<i128>;
+
+    /// Balance de `account` al final de `ledger` (debe ser un ledger pasado)
+    fn balance_at(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError>;
+
+    /// Supply total al final de `ledger` (debe ser un ledger pasado)
+    fn total_supply_at(env: Env, ledger: u32) -> Result<i128, TokenError>;
     fn admin(env: Env) -> Address;
+    fn pending_admin(env: Env) -> Option<Address>;
+
+    /// Versión del código del contrato: 1 al inicializar, +1 por upgrade()
+    fn version(env: Env) -> u32;
+}
+
+/// Trait de control de acceso por roles
+///
+/// Cada rol habilita una función privilegiada concreta (ver `Role`),
+/// así se puede entregar por ejemplo una key de minteo a un bot sin
+/// darle control sobre todo el token. El admin tiene todos los roles
+/// implícitamente
+pub trait AccessControlTrait {
+    /// Otorga `role` a `account`
+    ///
+    /// Requiere autorización de `caller` con el rol RoleAdmin
+    fn grant_role(env: Env, caller: Address, account: Address, role: Role) -> Result<(), TokenError>;
+
+    /// Revoca `role` de `account`
+    ///
+    /// Requiere autorización de `caller` con el rol RoleAdmin
+    fn revoke_role(env: Env, caller: Address, account: Address, role: Role) -> Result<(), TokenError>;
+
+    /// `account` renuncia a uno de sus propios roles
+    ///
+    /// Requiere autorización de `account`
+    fn renounce_role(env: Env, account: Address, role: Role) -> Result<(), TokenError>;
+
+    /// Consulta si `account` tiene `role` (true siempre para el admin)
+    fn has_role(env: Env, account: Address, role: Role) -> bool;
+
+    /// Configura el cupo de minteo de `minter` (solo admin)
+    ///
+    /// - total: cantidad máxima que puede mintear desde ahora
+    /// - window_ledgers: tamaño de la ventana de rate limit (0 = sin ventana)
+    /// - window_limit: máximo a mintear dentro de cada ventana
+    ///
+    /// Reemplaza el cupo anterior; total = 0 deja al minter sin cupo
+    fn set_minter_quota(
+        env: Env,
+        minter: Address,
+        total: i128,
+        window_ledgers: u32,
+        window_limit: i128
+    ) -> Result<(), TokenError>;
+
+    /// Consulta el cupo de minteo restante de `minter`
+    fn minter_quota(env: Env, minter: Address) -> Option<MinterQuota>;
+}
+
+/// Trait del circuit breaker (pausa de emergencia)
+///
+/// Permite detener toda la actividad del token en un solo ledger
+/// cuando se detecta un incidente, o solo una parte de ella
+pub trait PausableTrait {
+    /// Pausa las operaciones de `scope`
+    ///
+    /// - Transfers: transfer, transfer_from, approve, burn y burn_from
+    /// - Minting: mint
+    /// - All: ambos grupos
+    ///
+    /// Requiere autorización de `caller` con el rol Pauser
+    fn pause(env: Env, caller: Address, scope: PauseScope) -> Result<(), TokenError>;
+
+    /// Reanuda las operaciones de `scope`
+    ///
+    /// Requiere autorización de `caller` con el rol Pauser
+    fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), TokenError>;
+
+    /// true si cualquier grupo de operaciones está pausado
+    fn paused(env: Env) -> bool;
+
+    /// Estado detallado de la pausa
+    fn pause_state(env: Env) -> PauseState;
+}
+
+/// Trait de cumplimiento regulatorio
+///
+/// Controles por cuenta para emisiones reguladas, con la misma
+/// semántica que el Stellar Asset Contract
+pub trait ComplianceTrait {
+    /// Congela (authorize = false) o descongela una cuenta (solo admin)
+    ///
+    /// Una cuenta congelada no puede enviar, recibir ni quemar tokens,
+    /// y nadie puede gastar sus tokens con transfer_from/burn_from
+    fn set_authorized(env: Env, id: Address, authorize: bool) -> Result<(), TokenError>;
+
+    /// true si la cuenta no está congelada
+    fn authorized(env: Env, id: Address) -> bool;
+
+    /// Recupera `amount` tokens de `from` sin su autorización
+    ///
+    /// Reduce el balance y el supply total. Solo disponible si el
+    /// token se inicializó con clawback_enabled = true. Funciona
+    /// también sobre cuentas congeladas y con el token pausado.
+    /// Requiere autorización de `caller` con el rol Burner
+    fn clawback(env: Env, caller: Address, from: Address, amount: i128) -> Result<(), TokenError>;
+
+    /// Flag fijado en initialize(); los holders saben si puede haber clawback
+    fn clawback_enabled(env: Env) -> bool;
+
+    /// Agrega (allowed = true) o quita cuentas de la allowlist
+    ///
+    /// Requiere autorización de `caller` con el rol ComplianceManager.
+    /// Acepta hasta max_batch_size() cuentas por llamada y emite un
+    /// evento por cuenta. Quitar una cuenta no mueve su balance: solo
+    /// le impide recibir más tokens
+    fn set_allowlisted(
+        env: Env,
+        caller: Address,
+        accounts: Vec<Address>,
+        allowed: bool
+    ) -> Result<(), TokenError>;
+
+    /// true si la cuenta está en la allowlist
+    fn allowlisted(env: Env, account: Address) -> bool;
+
+    /// Flag fijado en initialize(): si true, solo cuentas de la
+    /// allowlist pueden recibir tokens (mint, transfer, transfer_from...)
+    fn allowlist_enabled(env: Env) -> bool;
+
+    /// Agrega (denied = true) o quita cuentas de la denylist (sanciones)
+    ///
+    /// Requiere autorización de `caller` con el rol SanctionsManager.
+    /// Una cuenta de la denylist no puede enviar, recibir, quemar,
+    /// aprobar ni gastar allowances, sin pausar el resto del token.
+    /// Acepta hasta max_batch_size() cuentas y emite un evento por cuenta
+    fn set_denylisted(
+        env: Env,
+        caller: Address,
+        accounts: Vec<Address>,
+        denied: bool
+    ) -> Result<(), TokenError>;
+
+    /// true si la cuenta está en la denylist
+    fn denylisted(env: Env, account: Address) -> bool;
+}
+
+/// Trait de approvals sin gas (permits firmados off-chain)
+///
+/// El owner firma con su clave ed25519 y cualquier relayer envía
+/// la transacción, así el owner no necesita su propia transacción
+pub trait PermitTrait {
+    /// Escribe un allowance como approve() usando una firma del owner
+    ///
+    /// `signature` es la firma ed25519 de permit_digest(...) hecha
+    /// con la clave de `owner_pubkey`; el owner es la cuenta G...
+    /// de esa clave. `nonce` debe ser igual a permit_nonce(owner)
+    /// y se incrementa en cada permit, así una firma no se reutiliza.
+    ///
+    /// Nota: solo se verifica la clave maestra de la cuenta, no los
+    /// signers ni thresholds configurados en el ledger
+    fn permit(
+        env: Env,
+        owner_pubkey: BytesN<32>,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+        signature: BytesN<64>
+    ) -> Result<(), TokenError>;
+
+    /// Próximo nonce válido para los permits de `owner`
+    fn permit_nonce(env: Env, owner: Address) -> u64;
+
+    /// Mensaje (hash de 32 bytes) que el owner debe firmar
+    fn permit_digest(
+        env: Env,
+        owner_pubkey: BytesN<32>,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64
+    ) -> BytesN<32>;
+}
+
+/// Trait de operaciones en lote
+///
+/// Una sola invocación para distribuciones de muchos destinatarios
+/// (nóminas, airdrops): una autorización y un solo débito del sender
+pub trait BatchTrait {
+    /// Transfiere desde `from` a cada (destinatario, amount) de `recipients`
+    ///
+    /// Requiere autorización de `from` una sola vez. Todas las partidas
+    /// se validan antes de mover balances y el lote es atómico: si una
+    /// partida es inválida no se aplica ninguna. Emite un TransferEvent
+    /// por partida, igual que transfer()
+    fn batch_transfer(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError>;
+
+    /// Mintea a cada (destinatario, amount) de `recipients`
+    ///
+    /// Mismas reglas que mint() (rol Minter, cupo, tope de emisión,
+    /// pausa, cuentas congeladas) aplicadas al total del lote: una
+    /// autorización y una sola actualización del supply. Atómico.
+    /// Emite un MintEvent por partida
+    fn mint_batch(
+        env: Env,
+        minter: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError>;
+
+    /// Fija el máximo de partidas por lote (solo admin)
+    ///
+    /// Entre 1 y MAX_BATCH_SIZE, para que un lote completo quepa en
+    /// el límite de entradas escritas por transacción
+    fn set_max_batch_size(env: Env, max_batch_size: u32) -> Result<(), TokenError>;
+
+    /// Máximo de partidas por lote (DEFAULT_MAX_BATCH_SIZE si no se fijó)
+    fn max_batch_size(env: Env) -> u32;
+}
+
+/// Trait de airdrops por prueba de Merkle
+///
+/// El admin publica solo la raíz del árbol de (index, account, amount)
+/// y cada holder reclama su parte: el token no paga una escritura por
+/// destinatario al crear el airdrop
+pub trait AirdropTrait {
+    /// Crea un airdrop y mintea `amount` al contrato como reserva (solo admin)
+    ///
+    /// Los claims se aceptan hasta `deadline_ledger` inclusive. Solo
+    /// puede haber un airdrop en curso; el siguiente se crea después
+    /// de sweep_airdrop(). Respeta el tope de emisión y la pausa de minteo
+    fn start_airdrop(
+        env: Env,
+        merkle_root: BytesN<32>,
+        amount: i128,
+        deadline_ledger: u32
+    ) -> Result<(), TokenError>;
+
+    /// Transfiere `amount` desde la reserva a `account`
+    ///
+    /// `proof` son los hermanos desde la hoja airdrop_leaf(index,
+    /// account, amount) hasta la raíz. Cada index se reclama una sola
+    /// vez. No requiere autorización: cualquiera puede reclamar en
+    /// nombre de `account`, y los tokens siempre van a `account`
+    fn claim(
+        env: Env,
+        index: u32,
+        account: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>
+    ) -> Result<(), TokenError>;
+
+    /// Después del deadline, envía la reserva no reclamada a `to`
+    /// y cierra el airdrop (solo admin)
+    fn sweep_airdrop(env: Env, to: Address) -> Result<(), TokenError>;
+
+    /// Airdrop en curso, si hay uno
+    fn airdrop(env: Env) -> Option<Airdrop>;
+
+    /// true si el index ya se reclamó en el airdrop en curso
+    fn is_claimed(env: Env, index: u32) -> bool;
+
+    /// Hoja del árbol para (index, account, amount)
+    fn airdrop_leaf(env: Env, index: u32, account: Address, amount: i128) -> BytesN<32>;
+}
+
+/// Trait de vesting (calendarios lineales con cliff)
+///
+/// Las asignaciones del equipo e inversores se bloquean en el
+/// contrato y se liberan según el número de ledger
+pub trait VestingTrait {
+    /// Crea un calendario y mintea `total` al contrato (solo admin)
+    ///
+    /// Nada se libera antes de `cliff_ledger`; desde ahí el monto
+    /// liberado crece linealmente de `start_ledger` a `end_ledger`.
+    /// Respeta el tope de emisión y la pausa de minteo. Devuelve el id
+    fn create_vesting(
+        env: Env,
+        beneficiary: Address,
+        total: i128,
+        start_ledger: u32,
+        cliff_ledger: u32,
+        end_ledger: u32,
+        revocable: bool
+    ) -> Result<u32, TokenError>;
+
+    /// Paga al beneficiario todo lo liberado y todavía no pagado
+    ///
+    /// Requiere autorización del beneficiario. Devuelve el monto pagado
+    fn release(env: Env, id: u32) -> Result<i128, TokenError>;
+
+    /// Cancela un calendario revocable (solo admin)
+    ///
+    /// Lo ya liberado sigue disponible para release(); la parte no
+    /// liberada se envía a `to`
+    fn revoke_vesting(env: Env, id: u32, to: Address) -> Result<(), TokenError>;
+
+    /// Calendario con ese id, si existe
+    fn vesting(env: Env, id: u32) -> Option<VestingSchedule>;
+
+    /// Monto que release() pagaría ahora
+    fn releasable(env: Env, id: u32) -> i128;
+}
+
+/// Trait de votos para gobernanza on-chain (estilo ERC20Votes)
+///
+/// El poder de voto de una cuenta es la suma de los balances que le
+/// delegaron. Un balance sin delegar no cuenta: para votar con los
+/// propios tokens hay que delegar en uno mismo
+pub trait VotesTrait {
+    /// Delega todo el poder de voto de `delegator` en `delegatee`
+    ///
+    /// Requiere autorización de `delegator`. Los cambios posteriores
+    /// de su balance se reflejan automáticamente en los votos del delegado
+    fn delegate(env: Env, delegator: Address, delegatee: Address) -> Result<(), TokenError>;
+
+    /// Delegado actual de `account`, si delegó
+    fn delegates(env: Env, account: Address) -> Option<Address>;
+
+    /// Votos actuales de `account`
+    fn get_votes(env: Env, account: Address) -> i128;
+
+    /// Votos de `account` al final de `ledger` (debe ser un ledger pasado)
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError>;
+}
+
+/// Trait de comisión por transferencia
+///
+/// Una comisión opcional sobre transfer, transfer_from y
+/// batch_transfer que va a la tesorería del fondo comunitario
+pub trait FeeTrait {
+    /// Fija (Some) o elimina (None) la política de comisión (solo admin)
+    ///
+    /// `bps` no puede superar MAX_FEE_BPS y `min_fee` no puede ser
+    /// mayor que `max_fee`. La comisión cobrada nunca supera MAX_FEE_BPS
+    /// del monto, aunque `min_fee` sea mayor
+    fn set_fee_policy(env: Env, policy: Option<FeePolicy>) -> Result<(), TokenError>;
+
+    /// Política de comisión vigente, si hay una
+    fn fee_policy(env: Env) -> Option<FeePolicy>;
+
+    /// Comisión que pagaría una transferencia de `amount` de `from` a `to`
+    fn transfer_fee(env: Env, from: Address, to: Address, amount: i128) -> i128;
+}
+
+/// Trait de timelock para operaciones administrativas sensibles
+///
+/// Con un delay configurado, las operaciones de AdminOp (upgrade,
+/// cambio de admin, comisión, tope de emisión, unpause) se programan
+/// con schedule() y solo se ejecutan después del delay: los holders
+/// tienen una ventana garantizada para salir antes de cada cambio
+pub trait TimelockTrait {
+    /// Fija el delay mínimo entre schedule() y execute() (solo admin)
+    ///
+    /// Solo se puede llamar directamente mientras el delay es 0;
+    /// después se cambia con AdminOp::SetTimelockDelay. Máximo
+    /// MAX_TIMELOCK_DELAY ledgers
+    fn set_timelock_delay(env: Env, delay_ledgers: u32) -> Result<(), TokenError>;
+
+    /// Delay en ledgers (0 = sin timelock)
+    fn timelock_delay(env: Env) -> u32;
+
+    /// Programa `op` para `eta_ledger` (solo admin); devuelve su id
+    ///
+    /// `eta_ledger` debe ser al menos el ledger actual + timelock_delay(),
+    /// y la operación se puede ejecutar hasta TIMELOCK_EXECUTION_WINDOW
+    /// ledgers después (InvalidExpiration si eso supera el TTL máximo).
+    /// `approvers` solo se usa con un admin multisig (ver MultisigTrait)
+    fn schedule(env: Env, approvers: Vec<Address>, op: AdminOp, eta_ledger: u32) -> Result<u32, TokenError>;
+
+    /// Ejecuta una operación programada cuyo eta ya llegó (solo admin)
+    fn execute(env: Env, approvers: Vec<Address>, op_id: u32) -> Result<(), TokenError>;
+
+    /// Cancela una operación programada (solo admin)
+    fn cancel(env: Env, approvers: Vec<Address>, op_id: u32) -> Result<(), TokenError>;
+
+    /// Operación programada con ese id, si existe
+    fn scheduled_op(env: Env, op_id: u32) -> Option<ScheduledOp>;
+}
+
+/// Trait de admin multisig (M-of-N) integrado en el token
+///
+/// Con un conjunto de firmantes configurado, admin() devuelve la
+/// dirección del propio contrato y las funciones de admin directas
+/// devuelven ThresholdNotMet: las operaciones privilegiadas se hacen
+/// con multisig_execute() (o schedule()/execute() con timelock),
+/// pasando al menos `threshold` firmantes en `approvers`
+pub trait MultisigTrait {
+    /// Reemplaza al admin por un conjunto de firmantes (o cambia el actual)
+    ///
+    /// Requiere al admin de una sola cuenta o `threshold` firmantes del
+    /// conjunto vigente. Con timelock, vía AdminOp::SetAdminSigners.
+    /// Se vuelve a un admin de una sola cuenta con propose_admin/accept_admin
+    fn set_admin_signers(env: Env, approvers: Vec<Address>, signers: SignerSet) -> Result<(), TokenError>;
+
+    /// Conjunto de firmantes vigente (None = admin de una sola cuenta)
+    fn admin_signers(env: Env) -> Option<SignerSet>;
+
+    /// Aplica `op` con la aprobación de `approvers` (solo admin)
+    ///
+    /// Las operaciones sensibles (upgrade, cambio de admin o de
+    /// firmantes, comisión, tope, unpause, delay) siguen sujetas al timelock
+    fn multisig_execute(env: Env, approvers: Vec<Address>, op: AdminOp) -> Result<(), TokenError>;
 }
 
 /// Estructura del contrato Token BDB
 #[contract]
 pub struct TokenBDB;
 
-/// Implementación del contrato
-#[contractimpl]
-impl TokenTrait for TokenBDB {
-    fn initialize(
-        env: Env, 
-        admin: Address, 
-        name: String, 
-        symbol: String,
-        decimals: u32
-    ) -> Result<(), TokenError> {
-        // 1. Verificar que no esté inicializado
-        if env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::AlreadyInitialized);
+/// Helpers internos compartidos por las funciones del contrato
+impl TokenBDB {
+    /// Aborta con NotInitialized si initialize() no fue llamado
+    fn require_initialized(env: &Env) {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            panic_with_error!(env, TokenError::NotInitialized);
         }
-        
-        // 2. Validar decimales (máximo 18 como Ethereum)
-        if decimals > MAX_DECIMALS {
-            return Err(TokenError::InvalidDecimals);
+        if let Err(error) = Self::check_schema(env) {
+            panic_with_error!(env, error);
         }
-        
-        // 3. Validar metadatos (name y symbol no vacíos)
-        // Nota: String en Soroban no tiene .len() directo,
-        // pero podemos convertir a bytes para validar
-        if name.len() == 0 || name.len() > MAX_NAME_LENGTH {
+    }
+
+    /// Devuelve ReservedAccount si `account` es el propio contrato
+    ///
+    /// Su balance es la reserva de airdrops y vesting: congelarlo,
+    /// sancionarlo o hacerle clawback rompería claim() y release()
+    fn require_not_reserve(env: &Env, account: &Address) -> Result<(), TokenError> {
+        if *account == env.current_contract_address() {
+            return Err(TokenError::ReservedAccount);
+        }
+        Ok(())
+    }
+
+    /// Devuelve InvalidMetadata si name o symbol están vacíos o son muy largos
+    fn validate_metadata(name: &String, symbol: &String) -> Result<(), TokenError> {
+        if name.is_empty() || name.len() > MAX_NAME_LENGTH {
             return Err(TokenError::InvalidMetadata);
         }
-        
-        if symbol.len() == 0 || symbol.len() > MAX_SYMBOL_LENGTH {
+
+        if symbol.is_empty() || symbol.len() > MAX_SYMBOL_LENGTH {
             return Err(TokenError::InvalidMetadata);
         }
-        
-        // 4. Guardar metadata en instance storage
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::TokenName, &name);
-        env.storage().instance().set(&DataKey::TokenSymbol, &symbol);
-        env.storage().instance().set(&DataKey::Decimals, &decimals);
-        env.storage().instance().set(&DataKey::TotalSupply, &0i128);
-        env.storage().instance().set(&DataKey::Initialized, &true);
-        
-        // 5. Extender TTL del storage de instance (30 días)
-        env.storage().instance().extend_ttl(100_000, 200_000);
-        
-        // 6. Emitir evento rico con todos los metadatos
-        // [COM: Reemplazo de env.events().publish por el nuevo macro InitEvent().publish()]
-        InitEvent {
-            admin: admin.clone(),
-            name: name.clone(),
-            symbol: symbol.clone(),
-            decimals,
-        }.publish(&env);
-        
+
         Ok(())
     }
-    
-    fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
+
+    /// Devuelve SchemaOutdated si el storage no está migrado al esquema actual
+    fn check_schema(env: &Env) -> Result<(), TokenError> {
+        if Self::storage_version(env.clone()) < STORAGE_VERSION {
+            return Err(TokenError::SchemaOutdated);
+        }
+        Ok(())
+    }
+
+    /// Lee el admin y exige su autorización
+    ///
+    /// Devuelve AdminRenounced si el admin renunció al rol y
+    /// SchemaOutdated si falta ejecutar migrate()
+    fn require_admin(env: &Env) -> Result<Address, TokenError> {
+        let admin = Self::authorize_admin(env)?;
+        Self::check_schema(env)?;
+        Ok(admin)
+    }
+
+    /// Como require_admin() pero sin exigir el esquema actual
+    ///
+    /// Solo para upgrade() y migrate(), que deben funcionar
+    /// justamente cuando el esquema está desactualizado
+    fn authorize_admin(env: &Env) -> Result<Address, TokenError> {
+        Self::authorize_approvers(env, &Vec::new(env))
+    }
+
+    /// Exige la autorización del admin (sin exigir el esquema actual)
+    ///
+    /// Con un admin multisig, `approvers` debe tener al menos
+    /// `threshold` firmantes distintos del conjunto, y cada uno
+    /// autoriza la llamada. Con un admin de una sola cuenta se ignora
+    fn authorize_approvers(env: &Env, approvers: &Vec<Address>) -> Result<Address, TokenError> {
         if !env.storage().instance().has(&DataKey::Initialized) {
             return Err(TokenError::NotInitialized);
         }
-        
-        // 2. Solo el admin puede mintear
+
         let admin: Address = env.storage().instance()
             .get(&DataKey::Admin)
-            .ok_or(TokenError::NotInitialized)?;
-        admin.require_auth();
-        
-        // 3. Validaciones
+            .ok_or(TokenError::AdminRenounced)?;
+
+        match Self::admin_signers(env.clone()) {
+            None => admin.require_auth(),
+            Some(set) => {
+                if approvers.len() < set.threshold {
+                    return Err(TokenError::ThresholdNotMet);
+                }
+                for (index, approver) in approvers.iter().enumerate() {
+                    // Solo firmantes del conjunto, cada uno una vez
+                    if !set.signers.contains(&approver)
+                        || approvers.first_index_of(&approver) != Some(index as u32)
+                    {
+                        return Err(TokenError::Unauthorized);
+                    }
+                    approver.require_auth();
+                }
+            }
+        }
+
+        Ok(admin)
+    }
+
+    /// Exige autorización de `caller` y que tenga `role`
+    ///
+    /// El admin pasa cualquier verificación de rol
+    fn require_role(env: &Env, caller: &Address, role: Role) -> Result<(), TokenError> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        Self::check_schema(env)?;
+
+        // Un admin multisig solo actúa vía multisig_execute()
+        if *caller == env.current_contract_address() {
+            return Err(TokenError::ThresholdNotMet);
+        }
+
+        caller.require_auth();
+
+        if !Self::has_role(env.clone(), caller.clone(), role) {
+            return Err(TokenError::Unauthorized);
+        }
+
+        Ok(())
+    }
+
+    /// Devuelve TimelockRequired si hay un timelock configurado
+    ///
+    /// Las operaciones sensibles (AdminOp) solo se aplican
+    /// directamente mientras timelock_delay() es 0
+    fn require_no_timelock(env: &Env) -> Result<(), TokenError> {
+        if Self::timelock_delay(env.clone()) > 0 {
+            return Err(TokenError::TimelockRequired);
+        }
+        Ok(())
+    }
+
+    /// Si `op` pasa por el timelock cuando hay un delay configurado
+    ///
+    /// Solo las operaciones sensibles; las demás tampoco esperan
+    /// con un admin de una sola cuenta
+    fn requires_timelock(op: &AdminOp) -> bool {
+        matches!(
+            op,
+            AdminOp::Upgrade(..)
+                | AdminOp::ProposeAdmin(..)
+                | AdminOp::SetFeePolicy(..)
+                | AdminOp::RemoveFeePolicy
+                | AdminOp::SetMaxSupply(..)
+                | AdminOp::Unpause(..)
+                | AdminOp::SetTimelockDelay(..)
+                | AdminOp::SetAdminSigners(..)
+        )
+    }
+
+    /// Aplica una operación administrativa (directa o vía execute())
+    fn apply_admin_op(env: &Env, admin: Address, op: AdminOp) -> Result<(), TokenError> {
+        // Como require_admin(): solo upgrade y migrate con el esquema desactualizado
+        if !matches!(op, AdminOp::Upgrade(..) | AdminOp::Migrate(..)) {
+            Self::check_schema(env)?;
+        }
+
+        match op {
+            AdminOp::Mint(to, amount) => Self::apply_mint(env, &admin, to, amount)?,
+            AdminOp::SetMinterQuota(minter, total, window_ledgers, window_limit) => {
+                Self::apply_set_minter_quota(env, minter, total, window_ledgers, window_limit)?
+            }
+            AdminOp::SetAuthorized(id, authorize) => Self::apply_set_authorized(env, id, authorize)?,
+            AdminOp::SetMaxBatchSize(max_batch_size) => {
+                Self::apply_set_max_batch_size(env, admin, max_batch_size)?
+            }
+            AdminOp::StartAirdrop(merkle_root, amount, deadline_ledger) => {
+                Self::apply_start_airdrop(env, admin, merkle_root, amount, deadline_ledger)?
+            }
+            AdminOp::SweepAirdrop(to) => Self::apply_sweep_airdrop(env, admin, to)?,
+            AdminOp::CreateVesting(beneficiary, total, start_ledger, cliff_ledger, end_ledger, revocable) => {
+                Self::apply_create_vesting(
+                    env,
+                    beneficiary,
+                    total,
+                    start_ledger,
+                    cliff_ledger,
+                    end_ledger,
+                    revocable
+                )?;
+            }
+            AdminOp::RevokeVesting(id, to) => Self::apply_revoke_vesting(env, id, to)?,
+            AdminOp::RenounceAdmin => Self::apply_renounce_admin(env, admin),
+            AdminOp::GrantRole(account, role) => Self::apply_grant_role(env, admin, account, role),
+            AdminOp::RevokeRole(account, role) => Self::apply_revoke_role(env, admin, account, role),
+            AdminOp::Migrate(allowances, complete) => {
+                Self::apply_migrate(env, admin, allowances, complete)?
+            }
+            AdminOp::SetAdminSigners(signers) => Self::apply_set_admin_signers(env, admin, signers)?,
+            AdminOp::Upgrade(new_wasm_hash) => Self::apply_upgrade(env, admin, new_wasm_hash),
+            AdminOp::ProposeAdmin(new_admin) => Self::apply_propose_admin(env, admin, new_admin),
+            AdminOp::SetFeePolicy(policy) => Self::apply_set_fee_policy(env, admin, Some(policy))?,
+            AdminOp::RemoveFeePolicy => Self::apply_set_fee_policy(env, admin, None)?,
+            AdminOp::SetMaxSupply(max_supply) => Self::apply_set_max_supply(env, admin, max_supply)?,
+            AdminOp::Unpause(scope) => Self::apply_unpause(env, admin, scope),
+            AdminOp::SetTimelockDelay(delay_ledgers) => {
+                Self::apply_set_timelock_delay(env, admin, delay_ledgers)?
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_upgrade(env: &Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        // Registrar la nueva versión antes de cambiar el código
+        let version = Self::version(env.clone()) + 1;
+        env.storage().instance().set(&DataKey::Version, &version);
+
+        // Reemplazar el WASM (efectivo desde la próxima invocación)
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+
+        UpgradeEvent { admin, new_wasm_hash, version }.publish(env);
+    }
+
+    fn apply_propose_admin(env: &Env, admin: Address, new_admin: Address) {
+        env.storage().instance().set(&DataKey::PendingAdmin, &new_admin);
+
+        AdminProposedEvent {
+            admin,
+            pending_admin: new_admin,
+        }.publish(env);
+    }
+
+    fn apply_set_max_supply(env: &Env, admin: Address, max_supply: i128) -> Result<(), TokenError> {
+        // Nunca por debajo del supply actual
+        if max_supply <= 0 || max_supply < Self::total_supply(env.clone()) {
+            return Err(TokenError::InvalidAmount);
+        }
+
+        // Un tope existente solo se puede bajar
+        if let Some(current) = Self::max_supply(env.clone()) {
+            if max_supply > current {
+                return Err(TokenError::SupplyCapExceeded);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::MaxSupply, &max_supply);
+        MaxSupplySetEvent { admin, max_supply }.publish(env);
+
+        Ok(())
+    }
+
+    fn apply_set_fee_policy(env: &Env, admin: Address, policy: Option<FeePolicy>) -> Result<(), TokenError> {
+        // Validaciones (techo fijo en el código)
+        match &policy {
+            Some(policy) => {
+                if policy.bps > MAX_FEE_BPS
+                    || policy.min_fee < 0
+                    || policy.max_fee < policy.min_fee
+                    || policy.exempt.len() > MAX_FEE_EXEMPT
+                {
+                    return Err(TokenError::InvalidFeePolicy);
+                }
+                env.storage().instance().set(&DataKey::FeePolicy, policy);
+            }
+            None => env.storage().instance().remove(&DataKey::FeePolicy),
+        }
+
+        FeePolicySetEvent { admin, policy }.publish(env);
+
+        Ok(())
+    }
+
+    /// `bps` basis points de `amount`, sin desbordar con montos grandes
+    fn bps_of(amount: i128, bps: u32) -> i128 {
+        amount / 10_000 * bps as i128 + amount % 10_000 * bps as i128 / 10_000
+    }
+
+    fn apply_unpause(env: &Env, caller: Address, scope: PauseScope) {
+        Self::set_pause_state(env, &scope, false);
+        UnpausedEvent { caller, scope }.publish(env);
+    }
+
+    /// Mintea `amount` a `to` (mint() o AdminOp::Mint)
+    ///
+    /// `minter` ya está autorizado; consume su cupo si no es el admin
+    fn apply_mint(env: &Env, minter: &Address, to: Address, amount: i128) -> Result<(), TokenError> {
+        // Circuit breaker
+        if Self::pause_state(env.clone()).minting {
+            return Err(TokenError::Paused);
+        }
+
+        // Validaciones
         if amount <= 0 {
             return Err(TokenError::InvalidAmount);
         }
-        
-        // 4. Validar que `to` no sea igual a `admin` (opcional, pero buena práctica)
-        // Esto evita que el admin se mintee tokens a sí mismo por error
-        
-        // 5. Obtener balance actual y verificar overflow
+
+        // Una cuenta congelada (o fuera de la allowlist) no puede recibir tokens
+        if !Self::authorized(env.clone(), to.clone()) {
+            return Err(TokenError::AccountFrozen);
+        }
+        if !Self::can_hold(env, &to) {
+            return Err(TokenError::NotAllowlisted);
+        }
+        if Self::denylisted(env.clone(), to.clone()) {
+            return Err(TokenError::AccountDenylisted);
+        }
+
+        // Descontar del cupo del minter (acota el daño de una key comprometida)
+        Self::consume_mint_quota(env, minter, amount)?;
+
+        // Obtener balance actual y verificar overflow
         let balance = Self::balance(env.clone(), to.clone());
         let new_balance = balance.checked_add(amount)
             .ok_or(TokenError::OverflowError)?;
-        
-        // 6. Actualizar balance con TTL extendido
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()), 
-            &new_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()),
-            100_000,
-            200_000
-        );
-        
-        // 7. Actualizar total supply
+
+        // Actualizar total supply
         let total: i128 = env.storage().instance()
             .get(&DataKey::TotalSupply)
             .unwrap_or(0);
         let new_total = total.checked_add(amount)
             .ok_or(TokenError::OverflowError)?;
-        env.storage().instance().set(
-            &DataKey::TotalSupply, 
-            &new_total
-        );
-        
-        // 8. Emitir evento detallado
-        // [COM: Reemplazo del env.events().publish con el nuevo macro MintEvent().publish()]
+        if new_total > Self::max_supply(env.clone()).unwrap_or(i128::MAX) {
+            return Err(TokenError::SupplyCapExceeded);
+        }
+        Self::write_total_supply(env, new_total);
+
+        // Actualizar balance con TTL extendido
+        Self::write_balance(env, &to, new_balance);
+
+        // Emitir evento SEP-41
         MintEvent {
             to: to.clone(),
             amount,
-            new_balance,
-            new_total,
+        }.publish(env);
+
+        Ok(())
+    }
+
+    fn apply_grant_role(env: &Env, caller: Address, account: Address, role: Role) {
+        let key = DataKey::Role(account.clone(), role);
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+
+        RoleGrantedEvent { role, account, caller }.publish(env);
+    }
+
+    fn apply_revoke_role(env: &Env, caller: Address, account: Address, role: Role) {
+        env.storage().persistent().remove(&DataKey::Role(account.clone(), role));
+
+        RoleRevokedEvent { role, account, caller }.publish(env);
+    }
+
+    fn apply_migrate(
+        env: &Env,
+        admin: Address,
+        allowances: Vec<(Address, Address)>,
+        complete: bool
+    ) -> Result<(), TokenError> {
+        if Self::check_schema(env).is_ok() {
+            return Err(TokenError::AlreadyMigrated);
+        }
+
+        // Lotes acotados para no exceder los recursos de una invocación
+        if allowances.len() > Self::max_batch_size(env.clone()) {
+            return Err(TokenError::BatchTooLarge);
+        }
+
+        // Reescribir cada allowance en el formato actual
+        for (from, spender) in allowances.iter() {
+            if let Some(value) = Self::read_allowance(env, &from, &spender) {
+                Self::write_allowance(env, &from, &spender, value.amount, value.expiration_ledger);
+            }
+        }
+
+        // Marcar el esquema como migrado
+        if complete {
+            env.storage().instance().set(&DataKey::StorageVersion, &STORAGE_VERSION);
+            MigratedEvent {
+                admin,
+                storage_version: STORAGE_VERSION,
+            }.publish(env);
+        }
+
+        Ok(())
+    }
+
+    fn apply_set_authorized(env: &Env, id: Address, authorize: bool) -> Result<(), TokenError> {
+        // La key solo existe si la cuenta está congelada
+        let key = DataKey::Frozen(id.clone());
+        if authorize {
+            env.storage().persistent().remove(&key);
+        } else {
+            Self::require_not_reserve(env, &id)?;
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+        }
+
+        SetAuthorizedEvent { id, authorize }.publish(env);
+
+        Ok(())
+    }
+
+    fn apply_set_minter_quota(
+        env: &Env,
+        minter: Address,
+        total: i128,
+        window_ledgers: u32,
+        window_limit: i128
+    ) -> Result<(), TokenError> {
+        if total < 0 || window_limit < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+
+        // La ventana empieza en el ledger actual
+        let key = DataKey::MinterQuota(minter.clone());
+        env.storage().persistent().set(&key, &MinterQuota {
+            remaining: total,
+            window_ledgers,
+            window_limit,
+            window_start: env.ledger().sequence(),
+            window_minted: 0,
+        });
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+
+        MinterQuotaSetEvent {
+            minter,
+            total,
+            window_ledgers,
+            window_limit,
+        }.publish(env);
+
+        Ok(())
+    }
+
+    fn apply_set_max_batch_size(env: &Env, admin: Address, max_batch_size: u32) -> Result<(), TokenError> {
+        if max_batch_size == 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        if max_batch_size > MAX_BATCH_SIZE {
+            return Err(TokenError::BatchTooLarge);
+        }
+
+        env.storage().instance().set(&DataKey::MaxBatchSize, &max_batch_size);
+        MaxBatchSizeSetEvent { admin, max_batch_size }.publish(env);
+
+        Ok(())
+    }
+
+    fn apply_start_airdrop(
+        env: &Env,
+        admin: Address,
+        merkle_root: BytesN<32>,
+        amount: i128,
+        deadline_ledger: u32
+    ) -> Result<(), TokenError> {
+        // Un solo airdrop en curso
+        if env.storage().instance().has(&DataKey::Airdrop) {
+            return Err(TokenError::AirdropActive);
+        }
+
+        // Validaciones
+        if Self::pause_state(env.clone()).minting {
+            return Err(TokenError::Paused);
+        }
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        if deadline_ledger < env.ledger().sequence() {
+            return Err(TokenError::InvalidExpiration);
+        }
+
+        // Mintear la reserva al propio contrato
+        let reserve = env.current_contract_address();
+        Self::mint_balance(env, &reserve, amount)?;
+
+        // Guardar el airdrop con un id nuevo (separa los bitmaps)
+        let id: u32 = env.storage().instance()
+            .get(&DataKey::AirdropCount)
+            .unwrap_or(0);
+        env.storage().instance().set(&DataKey::AirdropCount, &(id + 1));
+        env.storage().instance().set(&DataKey::Airdrop, &Airdrop {
+            id,
+            merkle_root: merkle_root.clone(),
+            deadline_ledger,
+            remaining: amount,
+        });
+
+        // Emitir eventos
+        MintEvent { to: reserve, amount }.publish(env);
+        AirdropStartedEvent {
+            admin,
+            id,
+            merkle_root,
+            amount,
+            deadline_ledger,
+        }.publish(env);
+
+        Ok(())
+    }
+
+    fn apply_sweep_airdrop(env: &Env, admin: Address, to: Address) -> Result<(), TokenError> {
+        // Solo después del deadline
+        let airdrop = Self::airdrop(env.clone())
+            .ok_or(TokenError::NoAirdrop)?;
+        if env.ledger().sequence() <= airdrop.deadline_ledger {
+            return Err(TokenError::AirdropNotEnded);
+        }
+
+        // Enviar la reserva no reclamada a `to`
+        let reserve = env.current_contract_address();
+        if airdrop.remaining > 0 {
+            Self::move_balance(env, &reserve, &to, airdrop.remaining);
+            TransferEvent {
+                from: reserve,
+                to,
+                amount: airdrop.remaining,
+            }.publish(env);
+        }
+
+        // Cerrar el airdrop y emitir evento
+        env.storage().instance().remove(&DataKey::Airdrop);
+        AirdropSweptEvent {
+            admin,
+            id: airdrop.id,
+            amount: airdrop.remaining,
+        }.publish(env);
+
+        Ok(())
+    }
+
+    fn apply_create_vesting(
+        env: &Env,
+        beneficiary: Address,
+        total: i128,
+        start_ledger: u32,
+        cliff_ledger: u32,
+        end_ledger: u32,
+        revocable: bool
+    ) -> Result<u32, TokenError> {
+        // Validaciones
+        if Self::pause_state(env.clone()).minting {
+            return Err(TokenError::Paused);
+        }
+        if total <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        if start_ledger >= end_ledger
+            || cliff_ledger < start_ledger
+            || cliff_ledger > end_ledger
+        {
+            return Err(TokenError::InvalidSchedule);
+        }
+
+        // Mintear los tokens bloqueados al propio contrato
+        let reserve = env.current_contract_address();
+        Self::mint_balance(env, &reserve, total)?;
+
+        // Guardar el calendario con un id nuevo
+        let id: u32 = env.storage().instance()
+            .get(&DataKey::VestingCount)
+            .unwrap_or(0);
+        env.storage().instance().set(&DataKey::VestingCount, &(id + 1));
+        Self::write_vesting(env, id, &VestingSchedule {
+            beneficiary: beneficiary.clone(),
+            total,
+            released: 0,
+            start_ledger,
+            cliff_ledger,
+            end_ledger,
+            revocable,
+            revoked: false,
+        });
+
+        // Emitir eventos
+        MintEvent { to: reserve, amount: total }.publish(env);
+        VestingCreatedEvent {
+            beneficiary,
+            id,
+            total,
+            start_ledger,
+            cliff_ledger,
+            end_ledger,
+            revocable,
+        }.publish(env);
+
+        Ok(id)
+    }
+
+    fn apply_revoke_vesting(env: &Env, id: u32, to: Address) -> Result<(), TokenError> {
+        // Solo calendarios revocables y no revocados
+        let mut schedule = Self::vesting(env.clone(), id)
+            .ok_or(TokenError::VestingNotFound)?;
+        if !schedule.revocable || schedule.revoked {
+            return Err(TokenError::NotRevocable);
+        }
+
+        // Congelar el calendario en lo liberado hasta ahora
+        let current_ledger = env.ledger().sequence();
+        let vested = vesting::vested_amount(&schedule, current_ledger)
+            .ok_or(TokenError::OverflowError)?;
+        let unvested = schedule.total - vested;
+        schedule.total = vested;
+        schedule.end_ledger = schedule.end_ledger.min(current_ledger);
+        schedule.cliff_ledger = schedule.cliff_ledger.min(schedule.end_ledger);
+        schedule.revoked = true;
+        Self::write_vesting(env, id, &schedule);
+
+        // Enviar la parte no liberada a `to`
+        let reserve = env.current_contract_address();
+        if unvested > 0 {
+            Self::move_balance(env, &reserve, &to, unvested);
+            TransferEvent {
+                from: reserve,
+                to,
+                amount: unvested,
+            }.publish(env);
+        }
+
+        // Emitir evento
+        VestingRevokedEvent {
+            beneficiary: schedule.beneficiary,
+            id,
+            unvested,
+        }.publish(env);
+
+        Ok(())
+    }
+
+    fn apply_renounce_admin(env: &Env, admin: Address) {
+        env.storage().instance().remove(&DataKey::Admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+        env.storage().instance().remove(&DataKey::AdminSigners);
+
+        AdminRenouncedEvent { admin }.publish(env);
+    }
+
+    fn apply_set_admin_signers(env: &Env, admin: Address, signers: SignerSet) -> Result<(), TokenError> {
+        // Umbral alcanzable y firmantes sin duplicados
+        let count = signers.signers.len();
+        if signers.threshold == 0 || signers.threshold > count || count > MAX_ADMIN_SIGNERS {
+            return Err(TokenError::InvalidSignerSet);
+        }
+        for (index, signer) in signers.signers.iter().enumerate() {
+            if signers.signers.first_index_of(&signer) != Some(index as u32) {
+                return Err(TokenError::InvalidSignerSet);
+            }
+        }
+
+        // El propio contrato pasa a ser el admin; una propuesta anterior
+        // no puede reemplazar al conjunto sin pasar por el umbral
+        env.storage().instance().set(&DataKey::Admin, &env.current_contract_address());
+        env.storage().instance().set(&DataKey::AdminSigners, &signers);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        AdminSignersSetEvent {
+            admin,
+            signers: signers.signers,
+            threshold: signers.threshold,
+        }.publish(env);
+
+        Ok(())
+    }
+
+    fn apply_set_timelock_delay(env: &Env, admin: Address, delay_ledgers: u32) -> Result<(), TokenError> {
+        if delay_ledgers > MAX_TIMELOCK_DELAY {
+            return Err(TokenError::InvalidAmount);
+        }
+
+        // Al activar el timelock, una propuesta de admin hecha sin
+        // espera no se puede aceptar: hay que programarla de nuevo
+        if Self::timelock_delay(env.clone()) == 0 && delay_ledgers > 0 {
+            env.storage().instance().remove(&DataKey::PendingAdmin);
+        }
+
+        env.storage().instance().set(&DataKey::TimelockDelay, &delay_ledgers);
+        TimelockDelaySetEvent { admin, delay_ledgers }.publish(env);
+
+        Ok(())
+    }
+
+    /// Descuenta `amount` del cupo de minteo de `minter`
+    ///
+    /// El admin no tiene cupo; cualquier otro minter necesita uno
+    /// configurado con set_minter_quota(). Si el cupo tiene ventana,
+    /// ésta se reinicia cuando pasan `window_ledgers` ledgers
+    fn consume_mint_quota(env: &Env, minter: &Address, amount: i128) -> Result<(), TokenError> {
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if admin.as_ref() == Some(minter) {
+            return Ok(());
+        }
+
+        let key = DataKey::MinterQuota(minter.clone());
+        let mut quota: MinterQuota = env.storage().persistent()
+            .get(&key)
+            .ok_or(TokenError::MintQuotaExceeded)?;
+
+        if amount > quota.remaining {
+            return Err(TokenError::MintQuotaExceeded);
+        }
+
+        if quota.window_ledgers > 0 {
+            let current_ledger = env.ledger().sequence();
+            if current_ledger >= quota.window_start.saturating_add(quota.window_ledgers) {
+                quota.window_start = current_ledger;
+                quota.window_minted = 0;
+            }
+
+            if amount > quota.window_limit - quota.window_minted {
+                return Err(TokenError::MintQuotaExceeded);
+            }
+            quota.window_minted += amount;
+        }
+
+        quota.remaining -= amount;
+        env.storage().persistent().set(&key, &quota);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+
+        // Los avisos salen con el mint que agota el cupo: un mint
+        // rechazado revierte la invocación y sus eventos
+        if quota.remaining == 0 {
+            MinterQuotaExhaustedEvent { minter: minter.clone() }.publish(env);
+        } else if quota.window_ledgers > 0 && quota.window_minted == quota.window_limit {
+            MinterWindowExhaustedEvent {
+                minter: minter.clone(),
+                window_end: quota.window_start.saturating_add(quota.window_ledgers),
+            }.publish(env);
+        }
+
+        Ok(())
+    }
+
+    /// Valida que un lote tenga entre 1 y max_batch_size() partidas
+    fn check_batch_size(env: &Env, recipients: &Vec<(Address, i128)>) -> Result<(), TokenError> {
+        if recipients.is_empty() {
+            return Err(TokenError::EmptyBatch);
+        }
+        if recipients.len() > Self::max_batch_size(env.clone()) {
+            return Err(TokenError::BatchTooLarge);
+        }
+        Ok(())
+    }
+
+    /// Aborta con Paused si las operaciones de balance están pausadas
+    fn require_transfers_enabled(env: &Env) {
+        if Self::pause_state(env.clone()).transfers {
+            panic_with_error!(env, TokenError::Paused);
+        }
+    }
+
+    /// Aborta con AccountFrozen si la cuenta está congelada
+    fn require_authorized(env: &Env, id: &Address) {
+        if !Self::authorized(env.clone(), id.clone()) {
+            panic_with_error!(env, TokenError::AccountFrozen);
+        }
+    }
+
+    /// Aborta con AccountDenylisted si la cuenta está en la denylist
+    fn require_not_denylisted(env: &Env, account: &Address) {
+        if Self::denylisted(env.clone(), account.clone()) {
+            panic_with_error!(env, TokenError::AccountDenylisted);
+        }
+    }
+
+    /// true si `account` puede recibir tokens según la allowlist
+    ///
+    /// Sin modo allowlist cualquier cuenta puede recibir
+    fn can_hold(env: &Env, account: &Address) -> bool {
+        !Self::allowlist_enabled(env.clone())
+            || Self::allowlisted(env.clone(), account.clone())
+    }
+
+    /// Aplica `paused` a los grupos de operaciones de `scope`
+    fn set_pause_state(env: &Env, scope: &PauseScope, paused: bool) {
+        let mut state = Self::pause_state(env.clone());
+        match scope {
+            PauseScope::All => {
+                state.transfers = paused;
+                state.minting = paused;
+            }
+            PauseScope::Transfers => state.transfers = paused,
+            PauseScope::Minting => state.minting = paused,
+        }
+        env.storage().instance().set(&DataKey::Paused, &state);
+    }
+
+    /// Escribe el balance de una cuenta con TTL extendido
+    ///
+    /// Optimización: si el balance queda en 0 se elimina la key.
+    /// Cada cambio queda registrado en el historial de la cuenta
+    fn write_balance(env: &Env, account: &Address, amount: i128) {
+        // Mover la diferencia entre los votos del delegado de la cuenta
+        let previous = Self::balance(env.clone(), account.clone());
+        if let Some(delegatee) = Self::delegates(env.clone(), account.clone()) {
+            Self::move_votes(env, None, Some(&delegatee), amount - previous);
+        }
+
+        let key = DataKey::Balance(account.clone());
+        if amount == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &amount);
+            env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+        }
+
+        checkpoints::push(
+            env,
+            &DataKey::BalanceHistory(account.clone()),
+            |index| DataKey::BalanceCheckpoint(account.clone(), index),
+            previous,
+            amount
+        );
+    }
+
+    /// Resta `amount` votos a `from` y los suma a `to`
+    ///
+    /// `None` es el lado sin delegado (tokens que entran o salen
+    /// del poder de voto). Un `amount` negativo mueve en sentido inverso
+    fn move_votes(env: &Env, from: Option<&Address>, to: Option<&Address>, amount: i128) {
+        if amount == 0 || from == to {
+            return;
+        }
+        if let Some(from) = from {
+            Self::write_votes(env, from, -amount);
+        }
+        if let Some(to) = to {
+            Self::write_votes(env, to, amount);
+        }
+    }
+
+    /// Suma `delta` a los votos de `delegatee` y lo registra en su historial
+    fn write_votes(env: &Env, delegatee: &Address, delta: i128) {
+        let head_key = DataKey::VotesHistory(delegatee.clone());
+        let previous_votes = checkpoints::latest(env, &head_key);
+        let new_votes = previous_votes.checked_add(delta)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::OverflowError));
+
+        checkpoints::push(
+            env,
+            &head_key,
+            |index| DataKey::VotesCheckpoint(delegatee.clone(), index),
+            previous_votes,
+            new_votes
+        );
+
+        DelegateVotesChangedEvent {
+            delegate: delegatee.clone(),
+            previous_votes,
+            new_votes,
+        }.publish(env);
+    }
+
+    /// Paga a la tesorería la comisión `fee` retenida de una transferencia de `from`
+    fn pay_fee(env: &Env, from: &Address, fee: i128) {
+        let Some(policy) = Self::fee_policy(env.clone()) else {
+            return;
+        };
+        if fee == 0 {
+            return;
+        }
+
+        Self::move_balance(env, from, &policy.treasury, fee);
+        TransferEvent {
+            from: from.clone(),
+            to: policy.treasury.clone(),
+            amount: fee,
+        }.publish(env);
+        FeeEvent {
+            from: from.clone(),
+            treasury: policy.treasury,
+            amount: fee,
+        }.publish(env);
+    }
+
+    /// Escribe el supply total y lo registra en su historial
+    fn write_total_supply(env: &Env, total: i128) {
+        let previous = Self::total_supply(env.clone());
+        env.storage().instance().set(&DataKey::TotalSupply, &total);
+        checkpoints::push(env, &DataKey::SupplyHistory, DataKey::SupplyCheckpoint, previous, total);
+    }
+
+    /// Aborta con FutureLedger si `ledger` no es un ledger pasado
+    ///
+    /// El ledger actual todavía puede cambiar dentro de la misma
+    /// transacción (por ejemplo con un préstamo flash)
+    fn require_past_ledger(env: &Env, ledger: u32) -> Result<(), TokenError> {
+        if ledger >= env.ledger().sequence() {
+            return Err(TokenError::FutureLedger);
+        }
+        Ok(())
+    }
+
+    /// Consume `amount` del allowance de `spender` sobre `from`
+    ///
+    /// Usado por transfer_from y burn_from. Si el allowance llega
+    /// a 0 se elimina la key (igual que approve con amount = 0)
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let allowed = Self::allowance(env.clone(), from.clone(), spender.clone());
+        if allowed < amount {
+            panic_with_error!(env, TokenError::InsufficientAllowance);
+        }
+
+        // El expiration_ledger no cambia al gastar parte del allowance
+        let value = Self::read_allowance(env, from, spender).unwrap();
+        Self::write_allowance(env, from, spender, allowed - amount, value.expiration_ledger);
+    }
+
+    /// Lee un allowance guardado, en el formato actual o en el del esquema 1
+    ///
+    /// Un allowance del esquema 1 (i128 sin expiración) se lee como
+    /// uno que vive lo máximo posible (max_live_until_ledger)
+    fn read_allowance(env: &Env, from: &Address, spender: &Address) -> Option<AllowanceValue> {
+        let raw: Val = env.storage().persistent()
+            .get(&DataKey::Allowance(from.clone(), spender.clone()))?;
+
+        AllowanceValue::try_from_val(env, &raw).ok().or_else(|| {
+            i128::try_from_val(env, &raw).ok().map(|amount| AllowanceValue {
+                amount,
+                expiration_ledger: env.ledger().max_live_until_ledger(),
+            })
+        })
+    }
+
+    /// Escribe un allowance con TTL ajustado a su expiración
+    ///
+    /// La entrada vive exactamente hasta `expiration_ledger`: después
+    /// vale 0 de todas formas, así que no tiene sentido pagar más TTL.
+    /// Si amount = 0 se elimina la key
+    fn write_allowance(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32
+    ) {
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        if amount == 0 {
+            env.storage().persistent().remove(&key);
+            return;
+        }
+
+        let current_ledger = env.ledger().sequence();
+        if expiration_ledger < current_ledger
+            || expiration_ledger > env.ledger().max_live_until_ledger()
+        {
+            panic_with_error!(env, TokenError::InvalidExpiration);
+        }
+
+        env.storage().persistent().set(
+            &key,
+            &AllowanceValue { amount, expiration_ledger }
+        );
+
+        let live_for = expiration_ledger - current_ledger;
+        env.storage().persistent().extend_ttl(&key, live_for, live_for);
+    }
+
+    /// Aumenta el balance de `to` y el supply total respetando max_supply
+    fn mint_balance(env: &Env, to: &Address, amount: i128) -> Result<(), TokenError> {
+        let total: i128 = env.storage().instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0);
+        let new_total = total.checked_add(amount)
+            .ok_or(TokenError::OverflowError)?;
+        if new_total > Self::max_supply(env.clone()).unwrap_or(i128::MAX) {
+            return Err(TokenError::SupplyCapExceeded);
+        }
+
+        let balance = Self::balance(env.clone(), to.clone());
+        let new_balance = balance.checked_add(amount)
+            .ok_or(TokenError::OverflowError)?;
+
+        Self::write_total_supply(env, new_total);
+        Self::write_balance(env, to, new_balance);
+        Ok(())
+    }
+
+    /// Guarda un calendario de vesting con TTL extendido
+    fn write_vesting(env: &Env, id: u32, schedule: &VestingSchedule) {
+        let key = DataKey::Vesting(id);
+        env.storage().persistent().set(&key, schedule);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+    }
+
+    /// Reduce el balance de `from` y el supply total (burn, burn_from y clawback)
+    fn burn_balance(env: &Env, from: &Address, amount: i128) {
+        let balance = Self::balance(env.clone(), from.clone());
+        if balance < amount {
+            panic_with_error!(env, TokenError::InsufficientBalance);
+        }
+        Self::write_balance(env, from, balance - amount);
+
+        let total: i128 = env.storage().instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0);
+        let new_total = total.checked_sub(amount)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::OverflowError));
+        Self::write_total_supply(env, new_total);
+    }
+
+    /// Mueve `amount` de `from` a `to` (transfer y transfer_from)
+    fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) {
+        // No permitir transferencia a sí mismo (gas-efficient)
+        if from == to {
+            panic_with_error!(env, TokenError::InvalidRecipient);
+        }
+
+        // Cuentas congeladas no envían ni reciben
+        Self::require_authorized(env, from);
+        Self::require_authorized(env, to);
+
+        // En modo allowlist solo cuentas aprobadas reciben
+        if !Self::can_hold(env, to) {
+            panic_with_error!(env, TokenError::NotAllowlisted);
+        }
+
+        // Cuentas sancionadas no envían ni reciben
+        Self::require_not_denylisted(env, from);
+        Self::require_not_denylisted(env, to);
+
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            panic_with_error!(env, TokenError::InsufficientBalance);
+        }
+
+        // Calcular nuevos balances con verificación de overflow
+        let to_balance = Self::balance(env.clone(), to.clone());
+        let new_to_balance = to_balance.checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::OverflowError));
+
+        Self::write_balance(env, from, from_balance - amount);
+        Self::write_balance(env, to, new_to_balance);
+    }
+}
+
+/// Implementación de las funciones administrativas
+#[contractimpl]
+impl TokenTrait for TokenBDB {
+    fn initialize(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+        decimals: u32,
+        config: TokenConfig
+    ) -> Result<(), TokenError> {
+        // 1. Verificar que no esté inicializado
+        if env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::AlreadyInitialized);
+        }
+
+        // 2. Validar decimales (máximo 18 como Ethereum)
+        if decimals > MAX_DECIMALS {
+            return Err(TokenError::InvalidDecimals);
+        }
+
+        // 3. Validar metadatos (name y symbol no vacíos)
+        Self::validate_metadata(&name, &symbol)?;
+
+        // 3b. Validar tope de emisión
+        if let Some(max_supply) = config.max_supply {
+            if max_supply <= 0 {
+                return Err(TokenError::InvalidAmount);
+            }
+            env.storage().instance().set(&DataKey::MaxSupply, &max_supply);
+        }
+
+        // 4. Guardar metadata en instance storage
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::TokenName, &name);
+        env.storage().instance().set(&DataKey::TokenSymbol, &symbol);
+        env.storage().instance().set(&DataKey::Decimals, &decimals);
+        env.storage().instance().set(&DataKey::TotalSupply, &0i128);
+        env.storage().instance().set(&DataKey::ClawbackEnabled, &config.clawback_enabled);
+        env.storage().instance().set(&DataKey::AllowlistEnabled, &config.allowlist_enabled);
+        env.storage().instance().set(&DataKey::Version, &1u32);
+        env.storage().instance().set(&DataKey::StorageVersion, &STORAGE_VERSION);
+        env.storage().instance().set(&DataKey::Initialized, &true);
+
+        // 5. Extender TTL del storage de instance (30 días)
+        env.storage().instance().extend_ttl(100_000, 200_000);
+
+        // 6. Emitir evento rico con todos los metadatos
+        // [COM: Reemplazo de env.events().publish por el nuevo macro InitEvent().publish()]
+        InitEvent {
+            admin: admin.clone(),
+            name: name.clone(),
+            symbol: symbol.clone(),
+            decimals,
+        }.publish(&env);
+
+        Ok(())
+    }
+
+    fn mint(env: Env, minter: Address, to: Address, amount: i128) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+
+        // 2. El minteo termina para siempre si el admin renunció
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(TokenError::AdminRenounced);
+        }
+
+        // 3. Solo cuentas con el rol Minter pueden mintear
+        Self::require_role(&env, &minter, Role::Minter)?;
+
+        // 4. Validar, mintear y emitir evento
+        Self::apply_mint(&env, &minter, to, amount)
+    }
+
+    fn propose_admin(env: Env, new_admin: Address) -> Result<(), TokenError> {
+        // 1. Solo el admin actual puede proponer (reemplaza propuestas previas)
+        let admin = Self::require_admin(&env)?;
+
+        // 2. Con timelock, solo vía schedule()/execute()
+        Self::require_no_timelock(&env)?;
+
+        // 3. Guardar admin pendiente y emitir evento
+        Self::apply_propose_admin(&env, admin, new_admin);
+
+        Ok(())
+    }
+
+    fn accept_admin(env: Env) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+
+        // 2. Debe existir una propuesta pendiente
+        let new_admin: Address = env.storage().instance()
+            .get(&DataKey::PendingAdmin)
+            .ok_or(TokenError::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        // 3. Aplicar el cambio
+        let old_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::AdminRenounced)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+        env.storage().instance().remove(&DataKey::AdminSigners);
+
+        // 4. Emitir evento
+        AdminChangedEvent {
+            old_admin,
+            new_admin,
+        }.publish(&env);
+
+        Ok(())
+    }
+
+    fn renounce_admin(env: Env) -> Result<(), TokenError> {
+        // 1. Solo el admin actual puede renunciar
+        let admin = Self::require_admin(&env)?;
+
+        // 2. Eliminar admin, firmantes y propuesta pendiente; emitir evento
+        Self::apply_renounce_admin(&env, admin);
+
+        Ok(())
+    }
+
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError> {
+        // 1. Solo el admin (también con el esquema desactualizado)
+        let admin = Self::authorize_admin(&env)?;
+
+        // 2. Con timelock, solo vía schedule()/execute()
+        Self::require_no_timelock(&env)?;
+
+        // 3. Reemplazar el WASM y emitir evento
+        Self::apply_upgrade(&env, admin, new_wasm_hash);
+
+        Ok(())
+    }
+
+    fn set_max_supply(env: Env, max_supply: i128) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = Self::require_admin(&env)?;
+
+        // 2. Con timelock, solo vía schedule()/execute()
+        Self::require_no_timelock(&env)?;
+
+        // 3. Validar, guardar y emitir evento
+        Self::apply_set_max_supply(&env, admin, max_supply)
+    }
+
+    fn total_supply(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+
+    fn max_supply(env: Env) -> Option<i128> {
+        env.storage().instance().get(&DataKey::MaxSupply)
+    }
+
+    fn balance_at(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError> {
+        Self::require_past_ledger(&env, ledger)?;
+        Ok(checkpoints::lookup(
+            &env,
+            &DataKey::BalanceHistory(account.clone()),
+            |index| DataKey::BalanceCheckpoint(account.clone(), index),
+            ledger,
+            Self::balance(env.clone(), account.clone())
+        ))
+    }
+
+    fn total_supply_at(env: Env, ledger: u32) -> Result<i128, TokenError> {
+        Self::require_past_ledger(&env, ledger)?;
+        Ok(checkpoints::lookup(
+            &env,
+            &DataKey::SupplyHistory,
+            DataKey::SupplyCheckpoint,
+            ledger,
+            Self::total_supply(env.clone())
+        ))
+    }
+
+    fn admin(env: Env) -> Address {
+        Self::require_initialized(&env);
+
+        env.storage().instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, TokenError::AdminRenounced))
+    }
+
+    fn migrate(
+        env: Env,
+        allowances: Vec<(Address, Address)>,
+        complete: bool
+    ) -> Result<(), TokenError> {
+        // 1. Solo el admin (con el esquema desactualizado)
+        let admin = Self::authorize_admin(&env)?;
+
+        // 2. Reescribir el lote y, si es el último, marcar el esquema
+        Self::apply_migrate(&env, admin, allowances, complete)
+    }
+
+    fn set_metadata(env: Env, caller: Address, metadata: TokenMetadata) -> Result<(), TokenError> {
+        // 1. Solo MetadataManager (o el admin)
+        Self::require_role(&env, &caller, Role::MetadataManager)?;
+
+        // 2. Validar (los decimales no cambian nunca)
+        Self::validate_metadata(&metadata.name, &metadata.symbol)?;
+        if metadata.decimals != Self::decimals(env.clone()) {
+            return Err(TokenError::InvalidDecimals);
+        }
+
+        // 3. Guardar en instance storage
+        env.storage().instance().set(&DataKey::TokenName, &metadata.name);
+        env.storage().instance().set(&DataKey::TokenSymbol, &metadata.symbol);
+
+        // 4. Emitir evento
+        MetadataUpdatedEvent {
+            caller,
+            name: metadata.name,
+            symbol: metadata.symbol,
+        }.publish(&env);
+
+        Ok(())
+    }
+
+    fn metadata(env: Env) -> TokenMetadata {
+        TokenMetadata {
+            name: Self::name(env.clone()),
+            symbol: Self::symbol(env.clone()),
+            decimals: Self::decimals(env),
+        }
+    }
+
+    fn storage_version(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::StorageVersion)
+            .unwrap_or(1)
+    }
+
+    fn version(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::Version)
+            .unwrap_or(1)
+    }
+
+    fn pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+}
+
+/// Implementación del control de acceso por roles
+#[contractimpl]
+impl AccessControlTrait for TokenBDB {
+    fn grant_role(env: Env, caller: Address, account: Address, role: Role) -> Result<(), TokenError> {
+        // 1. Solo RoleAdmin (o el admin) administra roles
+        Self::require_role(&env, &caller, Role::RoleAdmin)?;
+
+        // 2. Guardar el rol y emitir evento
+        Self::apply_grant_role(&env, caller, account, role);
+
+        Ok(())
+    }
+
+    fn revoke_role(env: Env, caller: Address, account: Address, role: Role) -> Result<(), TokenError> {
+        // 1. Solo RoleAdmin (o el admin) administra roles
+        Self::require_role(&env, &caller, Role::RoleAdmin)?;
+
+        // 2. Eliminar el rol y emitir evento
+        Self::apply_revoke_role(&env, caller, account, role);
+
+        Ok(())
+    }
+
+    fn renounce_role(env: Env, account: Address, role: Role) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+
+        // 2. Solo la propia cuenta puede renunciar a su rol
+        account.require_auth();
+        env.storage().persistent().remove(&DataKey::Role(account.clone(), role));
+
+        // 3. Emitir evento
+        RoleRevokedEvent { role, account: account.clone(), caller: account }.publish(&env);
+
+        Ok(())
+    }
+
+    fn has_role(env: Env, account: Address, role: Role) -> bool {
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if admin == Some(account.clone()) {
+            return true;
+        }
+
+        env.storage().persistent().has(&DataKey::Role(account, role))
+    }
+
+    fn set_minter_quota(
+        env: Env,
+        minter: Address,
+        total: i128,
+        window_ledgers: u32,
+        window_limit: i128
+    ) -> Result<(), TokenError> {
+        // 1. Solo el admin configura cupos
+        Self::require_admin(&env)?;
+
+        // 2. Validar, guardar y emitir evento
+        Self::apply_set_minter_quota(&env, minter, total, window_ledgers, window_limit)
+    }
+
+    fn minter_quota(env: Env, minter: Address) -> Option<MinterQuota> {
+        env.storage().persistent().get(&DataKey::MinterQuota(minter))
+    }
+}
+
+/// Implementación del circuit breaker
+#[contractimpl]
+impl PausableTrait for TokenBDB {
+    fn pause(env: Env, caller: Address, scope: PauseScope) -> Result<(), TokenError> {
+        // 1. Solo Pauser (o el admin)
+        Self::require_role(&env, &caller, Role::Pauser)?;
+
+        // 2. Actualizar estado
+        Self::set_pause_state(&env, &scope, true);
+
+        // 3. Emitir evento
+        PausedEvent { caller, scope }.publish(&env);
+
+        Ok(())
+    }
+
+    fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), TokenError> {
+        // 1. Solo Pauser (o el admin)
+        Self::require_role(&env, &caller, Role::Pauser)?;
+
+        // 2. Con timelock, solo vía schedule()/execute() (pause() sigue inmediato)
+        Self::require_no_timelock(&env)?;
+
+        // 3. Actualizar estado y emitir evento
+        Self::apply_unpause(&env, caller, scope);
+
+        Ok(())
+    }
+
+    fn paused(env: Env) -> bool {
+        let state = Self::pause_state(env);
+        state.transfers || state.minting
+    }
+
+    fn pause_state(env: Env) -> PauseState {
+        env.storage().instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(PauseState { transfers: false, minting: false })
+    }
+}
+
+/// Implementación de los controles de cumplimiento
+#[contractimpl]
+impl ComplianceTrait for TokenBDB {
+    fn set_authorized(env: Env, id: Address, authorize: bool) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        Self::require_admin(&env)?;
+
+        // 2. Guardar estado y emitir evento
+        Self::apply_set_authorized(&env, id, authorize)
+    }
+
+    fn authorized(env: Env, id: Address) -> bool {
+        !env.storage().persistent().has(&DataKey::Frozen(id))
+    }
+
+    fn clawback(env: Env, caller: Address, from: Address, amount: i128) -> Result<(), TokenError> {
+        // 1. Solo Burner (o el admin)
+        Self::require_role(&env, &caller, Role::Burner)?;
+
+        // 2. El token debe haberse creado con clawback habilitado
+        if !Self::clawback_enabled(env.clone()) {
+            return Err(TokenError::ClawbackDisabled);
+        }
+
+        // 3. Validaciones (la reserva de airdrops y vesting no se toca)
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        Self::require_not_reserve(&env, &from)?;
+
+        // 4. Reducir balance y total supply (sin autorización del holder)
+        Self::burn_balance(&env, &from, amount);
+
+        // 5. Emitir evento
+        ClawbackEvent { from, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    fn clawback_enabled(env: Env) -> bool {
+        env.storage().instance()
+            .get(&DataKey::ClawbackEnabled)
+            .unwrap_or(false)
+    }
+
+    fn set_allowlisted(
+        env: Env,
+        caller: Address,
+        accounts: Vec<Address>,
+        allowed: bool
+    ) -> Result<(), TokenError> {
+        // 1. Solo ComplianceManager (o el admin)
+        Self::require_role(&env, &caller, Role::ComplianceManager)?;
+
+        // 2. Validar tamaño del lote
+        if accounts.is_empty() {
+            return Err(TokenError::EmptyBatch);
+        }
+        if accounts.len() > Self::max_batch_size(env.clone()) {
+            return Err(TokenError::BatchTooLarge);
+        }
+
+        // 3. Guardar estado (la key solo existe si la cuenta está aprobada)
+        for account in accounts.iter() {
+            let key = DataKey::Allowlisted(account.clone());
+            if allowed {
+                env.storage().persistent().set(&key, &true);
+                env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+            } else {
+                env.storage().persistent().remove(&key);
+            }
+
+            // 4. Un evento por cuenta
+            AllowlistUpdatedEvent {
+                account,
+                allowed,
+                caller: caller.clone(),
+            }.publish(&env);
+        }
+
+        Ok(())
+    }
+
+    fn allowlisted(env: Env, account: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Allowlisted(account))
+    }
+
+    fn allowlist_enabled(env: Env) -> bool {
+        env.storage().instance()
+            .get(&DataKey::AllowlistEnabled)
+            .unwrap_or(false)
+    }
+
+    fn set_denylisted(
+        env: Env,
+        caller: Address,
+        accounts: Vec<Address>,
+        denied: bool
+    ) -> Result<(), TokenError> {
+        // 1. Solo SanctionsManager (o el admin)
+        Self::require_role(&env, &caller, Role::SanctionsManager)?;
+
+        // 2. Validar tamaño del lote
+        if accounts.is_empty() {
+            return Err(TokenError::EmptyBatch);
+        }
+        if accounts.len() > Self::max_batch_size(env.clone()) {
+            return Err(TokenError::BatchTooLarge);
+        }
+
+        // 3. Guardar estado (la key solo existe si la cuenta está sancionada)
+        for account in accounts.iter() {
+            let key = DataKey::Denylisted(account.clone());
+            if denied {
+                Self::require_not_reserve(&env, &account)?;
+                env.storage().persistent().set(&key, &true);
+                env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+            } else {
+                env.storage().persistent().remove(&key);
+            }
+
+            // 4. Un evento por cuenta
+            DenylistUpdatedEvent {
+                account,
+                denied,
+                caller: caller.clone(),
+            }.publish(&env);
+        }
+
+        Ok(())
+    }
+
+    fn denylisted(env: Env, account: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Denylisted(account))
+    }
+}
+
+/// Implementación de las operaciones en lote
+#[contractimpl]
+impl BatchTrait for TokenBDB {
+    fn batch_transfer(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización y circuit breaker
+        Self::require_initialized(&env);
+        if Self::pause_state(env.clone()).transfers {
+            return Err(TokenError::Paused);
+        }
+
+        // 2. Verificar autorización del sender (una sola vez para todo el lote)
+        from.require_auth();
+
+        // 3. Validar todas las partidas antes de tocar balances
+        Self::check_batch_size(&env, &recipients)?;
+        if !Self::authorized(env.clone(), from.clone()) {
+            return Err(TokenError::AccountFrozen);
+        }
+        if Self::denylisted(env.clone(), from.clone()) {
+            return Err(TokenError::AccountDenylisted);
+        }
+
+        let mut total: i128 = 0;
+        for (to, amount) in recipients.iter() {
+            if amount <= 0 {
+                return Err(TokenError::InvalidAmount);
+            }
+            if to == from {
+                return Err(TokenError::InvalidRecipient);
+            }
+            if !Self::authorized(env.clone(), to.clone()) {
+                return Err(TokenError::AccountFrozen);
+            }
+            if !Self::can_hold(&env, &to) {
+                return Err(TokenError::NotAllowlisted);
+            }
+            if Self::denylisted(env.clone(), to.clone()) {
+                return Err(TokenError::AccountDenylisted);
+            }
+            total = total.checked_add(amount)
+                .ok_or(TokenError::OverflowError)?;
+        }
+
+        // 3b. La tesorería recibe las comisiones con las mismas reglas
+        let policy = Self::fee_policy(env.clone());
+        if let Some(policy) = &policy {
+            if !Self::authorized(env.clone(), policy.treasury.clone()) {
+                return Err(TokenError::AccountFrozen);
+            }
+            if !Self::can_hold(&env, &policy.treasury) {
+                return Err(TokenError::NotAllowlisted);
+            }
+            if Self::denylisted(env.clone(), policy.treasury.clone()) {
+                return Err(TokenError::AccountDenylisted);
+            }
+        }
+
+        // 4. Debitar al sender una sola vez
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < total {
+            return Err(TokenError::InsufficientBalance);
+        }
+        Self::write_balance(&env, &from, from_balance - total);
+
+        // 5. Acreditar cada partida menos su comisión (un destinatario
+        // repetido acumula)
+        let mut fees: i128 = 0;
+        for (to, amount) in recipients.iter() {
+            let fee = Self::transfer_fee(env.clone(), from.clone(), to.clone(), amount);
+            fees += fee;
+
+            let to_balance = Self::balance(env.clone(), to.clone());
+            let new_to_balance = to_balance.checked_add(amount - fee)
+                .ok_or(TokenError::OverflowError)?;
+            Self::write_balance(&env, &to, new_to_balance);
+
+            // 6. Un evento SEP-41 por partida
+            TransferEvent {
+                from: from.clone(),
+                to,
+                amount: amount - fee,
+            }.publish(&env);
+        }
+
+        // 7. Las comisiones del lote se acreditan a la tesorería de una vez
+        // (salen del total ya debitado al sender)
+        if let (Some(policy), true) = (policy, fees > 0) {
+            let treasury_balance = Self::balance(env.clone(), policy.treasury.clone());
+            let new_treasury_balance = treasury_balance.checked_add(fees)
+                .ok_or(TokenError::OverflowError)?;
+            Self::write_balance(&env, &policy.treasury, new_treasury_balance);
+
+            TransferEvent {
+                from: from.clone(),
+                to: policy.treasury.clone(),
+                amount: fees,
+            }.publish(&env);
+            FeeEvent {
+                from,
+                treasury: policy.treasury,
+                amount: fees,
+            }.publish(&env);
+        }
+
+        Ok(())
+    }
+
+    fn mint_batch(
+        env: Env,
+        minter: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+
+        // 2. El minteo termina para siempre si el admin renunció
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(TokenError::AdminRenounced);
+        }
+
+        // 3. Solo cuentas con el rol Minter (una sola autorización)
+        Self::require_role(&env, &minter, Role::Minter)?;
+
+        // 3b. Circuit breaker
+        if Self::pause_state(env.clone()).minting {
+            return Err(TokenError::Paused);
+        }
+
+        // 4. Validar todas las partidas y sumar el total del lote
+        Self::check_batch_size(&env, &recipients)?;
+        let mut amount: i128 = 0;
+        for (to, leg_amount) in recipients.iter() {
+            if leg_amount <= 0 {
+                return Err(TokenError::InvalidAmount);
+            }
+            if !Self::authorized(env.clone(), to.clone()) {
+                return Err(TokenError::AccountFrozen);
+            }
+            if !Self::can_hold(&env, &to) {
+                return Err(TokenError::NotAllowlisted);
+            }
+            if Self::denylisted(env.clone(), to.clone()) {
+                return Err(TokenError::AccountDenylisted);
+            }
+            amount = amount.checked_add(leg_amount)
+                .ok_or(TokenError::OverflowError)?;
+        }
+
+        // 4b. Descontar el total del cupo del minter
+        Self::consume_mint_quota(&env, &minter, amount)?;
+
+        // 5. Actualizar total supply una sola vez
+        let total: i128 = env.storage().instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0);
+        let new_total = total.checked_add(amount)
+            .ok_or(TokenError::OverflowError)?;
+        if new_total > Self::max_supply(env.clone()).unwrap_or(i128::MAX) {
+            return Err(TokenError::SupplyCapExceeded);
+        }
+        Self::write_total_supply(&env, new_total);
+
+        // 6. Acreditar cada partida con TTL extendido
+        for (to, leg_amount) in recipients.iter() {
+            let balance = Self::balance(env.clone(), to.clone());
+            let new_balance = balance.checked_add(leg_amount)
+                .ok_or(TokenError::OverflowError)?;
+            Self::write_balance(&env, &to, new_balance);
+
+            // 7. Un evento SEP-41 por partida
+            MintEvent {
+                to,
+                amount: leg_amount,
+            }.publish(&env);
+        }
+
+        Ok(())
+    }
+
+    fn set_max_batch_size(env: Env, max_batch_size: u32) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = Self::require_admin(&env)?;
+
+        // 2. Validar, guardar y emitir evento
+        Self::apply_set_max_batch_size(&env, admin, max_batch_size)
+    }
+
+    fn max_batch_size(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::MaxBatchSize)
+            .unwrap_or(DEFAULT_MAX_BATCH_SIZE)
+    }
+}
+
+/// Implementación del airdrop por Merkle
+#[contractimpl]
+impl AirdropTrait for TokenBDB {
+    fn start_airdrop(
+        env: Env,
+        merkle_root: BytesN<32>,
+        amount: i128,
+        deadline_ledger: u32
+    ) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = Self::require_admin(&env)?;
+
+        // 2. Mintear la reserva, guardar el airdrop y emitir eventos
+        Self::apply_start_airdrop(&env, admin, merkle_root, amount, deadline_ledger)
+    }
+
+    fn claim(
+        env: Env,
+        index: u32,
+        account: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización y circuit breaker
+        Self::require_initialized(&env);
+        if Self::pause_state(env.clone()).transfers {
+            return Err(TokenError::Paused);
+        }
+
+        // 2. Airdrop en curso y dentro del plazo
+        let mut airdrop = Self::airdrop(env.clone())
+            .ok_or(TokenError::NoAirdrop)?;
+        if env.ledger().sequence() > airdrop.deadline_ledger {
+            return Err(TokenError::AirdropExpired);
+        }
+
+        // 3. Validaciones
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        let word_key = DataKey::AirdropClaimed(airdrop.id, index / 64);
+        let word: u64 = env.storage().persistent().get(&word_key).unwrap_or(0);
+        let bit = 1u64 << (index % 64);
+        if word & bit != 0 {
+            return Err(TokenError::AlreadyClaimed);
+        }
+
+        // 4. Verificar la prueba de Merkle
+        let leaf = airdrop::leaf_hash(&env, index, &account, amount);
+        if !airdrop::verify_proof(&env, &leaf, &proof, &airdrop.merkle_root) {
+            return Err(TokenError::InvalidProof);
+        }
+        if airdrop.remaining < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+        if !Self::authorized(env.clone(), account.clone()) {
+            return Err(TokenError::AccountFrozen);
+        }
+
+        // 5. Marcar el index como reclamado
+        env.storage().persistent().set(&word_key, &(word | bit));
+        env.storage().persistent().extend_ttl(&word_key, 100_000, 200_000);
+
+        // 6. Pagar desde la reserva
+        let reserve = env.current_contract_address();
+        Self::move_balance(&env, &reserve, &account, amount);
+        airdrop.remaining -= amount;
+        env.storage().instance().set(&DataKey::Airdrop, &airdrop);
+
+        // 7. Emitir eventos
+        TransferEvent {
+            from: reserve,
+            to: account.clone(),
+            amount,
+        }.publish(&env);
+        AirdropClaimedEvent {
+            account,
+            id: airdrop.id,
+            index,
+            amount,
+        }.publish(&env);
+
+        Ok(())
+    }
+
+    fn sweep_airdrop(env: Env, to: Address) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = Self::require_admin(&env)?;
+
+        // 2. Enviar la reserva no reclamada y cerrar el airdrop
+        Self::apply_sweep_airdrop(&env, admin, to)
+    }
+
+    fn airdrop(env: Env) -> Option<Airdrop> {
+        env.storage().instance().get(&DataKey::Airdrop)
+    }
+
+    fn is_claimed(env: Env, index: u32) -> bool {
+        let Some(airdrop) = Self::airdrop(env.clone()) else {
+            return false;
+        };
+        let word: u64 = env.storage().persistent()
+            .get(&DataKey::AirdropClaimed(airdrop.id, index / 64))
+            .unwrap_or(0);
+        word & (1u64 << (index % 64)) != 0
+    }
+
+    fn airdrop_leaf(env: Env, index: u32, account: Address, amount: i128) -> BytesN<32> {
+        airdrop::leaf_hash(&env, index, &account, amount)
+    }
+}
+
+/// Implementación de los calendarios de vesting
+#[contractimpl]
+impl VestingTrait for TokenBDB {
+    fn create_vesting(
+        env: Env,
+        beneficiary: Address,
+        total: i128,
+        start_ledger: u32,
+        cliff_ledger: u32,
+        end_ledger: u32,
+        revocable: bool
+    ) -> Result<u32, TokenError> {
+        // 1. Solo el admin
+        Self::require_admin(&env)?;
+
+        // 2. Mintear los tokens bloqueados, guardar y emitir eventos
+        Self::apply_create_vesting(
+            &env,
+            beneficiary,
+            total,
+            start_ledger,
+            cliff_ledger,
+            end_ledger,
+            revocable
+        )
+    }
+
+    fn release(env: Env, id: u32) -> Result<i128, TokenError> {
+        // 1. Verificar inicialización y circuit breaker
+        Self::require_initialized(&env);
+        if Self::pause_state(env.clone()).transfers {
+            return Err(TokenError::Paused);
+        }
+
+        // 2. Verificar autorización del beneficiario
+        let mut schedule = Self::vesting(env.clone(), id)
+            .ok_or(TokenError::VestingNotFound)?;
+        schedule.beneficiary.require_auth();
+
+        // 3. Calcular lo liberado y no pagado
+        let vested = vesting::vested_amount(&schedule, env.ledger().sequence())
+            .ok_or(TokenError::OverflowError)?;
+        let amount = vested - schedule.released;
+        if amount <= 0 {
+            return Err(TokenError::NothingToRelease);
+        }
+
+        // 4. Pagar desde el contrato
+        let reserve = env.current_contract_address();
+        Self::move_balance(&env, &reserve, &schedule.beneficiary, amount);
+        schedule.released = vested;
+        Self::write_vesting(&env, id, &schedule);
+
+        // 5. Emitir eventos
+        TransferEvent {
+            from: reserve,
+            to: schedule.beneficiary.clone(),
+            amount,
+        }.publish(&env);
+        VestingReleasedEvent {
+            beneficiary: schedule.beneficiary,
+            id,
+            amount,
+        }.publish(&env);
+
+        Ok(amount)
+    }
+
+    fn revoke_vesting(env: Env, id: u32, to: Address) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        Self::require_admin(&env)?;
+
+        // 2. Congelar el calendario y enviar la parte no liberada
+        Self::apply_revoke_vesting(&env, id, to)
+    }
+
+    fn vesting(env: Env, id: u32) -> Option<VestingSchedule> {
+        env.storage().persistent().get(&DataKey::Vesting(id))
+    }
+
+    fn releasable(env: Env, id: u32) -> i128 {
+        let Some(schedule) = Self::vesting(env.clone(), id) else {
+            return 0;
+        };
+        vesting::vested_amount(&schedule, env.ledger().sequence())
+            .map_or(0, |vested| vested - schedule.released)
+    }
+}
+
+/// Implementación de la delegación de votos
+#[contractimpl]
+impl VotesTrait for TokenBDB {
+    fn delegate(env: Env, delegator: Address, delegatee: Address) -> Result<(), TokenError> {
+        // 1. Verificar inicialización y autorización del delegator
+        Self::require_initialized(&env);
+        delegator.require_auth();
+
+        // 2. Guardar el nuevo delegado
+        let from_delegate = Self::delegates(env.clone(), delegator.clone());
+        let key = DataKey::Delegate(delegator.clone());
+        env.storage().persistent().set(&key, &delegatee);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+
+        // 3. Emitir evento antes de los DelegateVotesChanged
+        DelegateChangedEvent {
+            delegator: delegator.clone(),
+            from_delegate: from_delegate.clone(),
+            to_delegate: delegatee.clone(),
         }.publish(&env);
-        
+
+        // 4. Mover el balance del delegator al nuevo delegado
+        let balance = Self::balance(env.clone(), delegator);
+        Self::move_votes(&env, from_delegate.as_ref(), Some(&delegatee), balance);
+
         Ok(())
     }
-    
-    fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
+
+    fn delegates(env: Env, account: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Delegate(account))
+    }
+
+    fn get_votes(env: Env, account: Address) -> i128 {
+        checkpoints::latest(&env, &DataKey::VotesHistory(account))
+    }
+
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError> {
+        Self::require_past_ledger(&env, ledger)?;
+        Ok(checkpoints::lookup(
+            &env,
+            &DataKey::VotesHistory(account.clone()),
+            |index| DataKey::VotesCheckpoint(account.clone(), index),
+            ledger,
+            0
+        ))
+    }
+}
+
+/// Implementación de la comisión por transferencia
+#[contractimpl]
+impl FeeTrait for TokenBDB {
+    fn set_fee_policy(env: Env, policy: Option<FeePolicy>) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = Self::require_admin(&env)?;
+
+        // 2. Con timelock, solo vía schedule()/execute()
+        Self::require_no_timelock(&env)?;
+
+        // 3. Validar, guardar y emitir evento
+        Self::apply_set_fee_policy(&env, admin, policy)
+    }
+
+    fn fee_policy(env: Env) -> Option<FeePolicy> {
+        env.storage().instance().get(&DataKey::FeePolicy)
+    }
+
+    fn transfer_fee(env: Env, from: Address, to: Address, amount: i128) -> i128 {
+        let Some(policy) = Self::fee_policy(env) else {
+            return 0;
+        };
+
+        // La tesorería y las cuentas exentas no pagan ni generan comisión
+        if from == policy.treasury
+            || to == policy.treasury
+            || policy.exempt.contains(&from)
+            || policy.exempt.contains(&to)
+        {
+            return 0;
         }
-        
-        // 2. Requiere autorización del dueño de los tokens
-        from.require_auth();
-        
-        // 3. Validaciones
-        if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
+
+        // bps del monto, acotado a [min_fee, max_fee] y nunca más que
+        // MAX_FEE_BPS del monto (min_fee no puede saltarse el techo)
+        let fee = Self::bps_of(amount, policy.bps);
+        fee.clamp(policy.min_fee, policy.max_fee)
+            .min(Self::bps_of(amount, MAX_FEE_BPS))
+    }
+}
+
+/// Implementación del timelock administrativo
+#[contractimpl]
+impl TimelockTrait for TokenBDB {
+    fn set_timelock_delay(env: Env, delay_ledgers: u32) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = Self::require_admin(&env)?;
+
+        // 2. Una vez activo, el delay también pasa por el timelock
+        Self::require_no_timelock(&env)?;
+
+        // 3. Guardar y emitir evento
+        Self::apply_set_timelock_delay(&env, admin, delay_ledgers)
+    }
+
+    fn timelock_delay(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::TimelockDelay)
+            .unwrap_or(0)
+    }
+
+    fn schedule(env: Env, approvers: Vec<Address>, op: AdminOp, eta_ledger: u32) -> Result<u32, TokenError> {
+        // 1. Solo el admin (también con el esquema desactualizado, para upgrades)
+        Self::authorize_approvers(&env, &approvers)?;
+
+        // 2. El eta respeta el delay mínimo y la entrada vive hasta
+        //    TIMELOCK_EXECUTION_WINDOW ledgers después
+        let current_ledger = env.ledger().sequence();
+        let earliest = current_ledger.saturating_add(Self::timelock_delay(env.clone()));
+        if eta_ledger < earliest {
+            return Err(TokenError::TimelockTooEarly);
         }
-        
-        let balance = Self::balance(env.clone(), from.clone());
-        if balance < amount {
-            return Err(TokenError::InsufficientBalance);
+        let live_until = eta_ledger.saturating_add(TIMELOCK_EXECUTION_WINDOW);
+        if live_until > env.ledger().max_live_until_ledger() {
+            return Err(TokenError::InvalidExpiration);
         }
-        
-        // 4. Actualizar balance
-        let new_balance = balance - amount;
-        if new_balance == 0 {
-            // Optimización: eliminar key si balance = 0
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()),
-                &new_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        // 5. Actualizar total supply
-        let total: i128 = env.storage().instance()
-            .get(&DataKey::TotalSupply)
+
+        // 3. Guardar con un id nuevo
+        let op_id: u32 = env.storage().instance()
+            .get(&DataKey::TimelockCount)
             .unwrap_or(0);
-        let new_total = total.checked_sub(amount)
-            .ok_or(TokenError::OverflowError)?;
-        env.storage().instance().set(
-            &DataKey::TotalSupply,
-            &new_total
-        );
-        
-        // 6. Emitir evento
-        // [COM: Reemplazar env.events().publish con el nuevo macro BurnEvent().publish()]
-        BurnEvent {
-            from: from.clone(),
-            amount,
-            new_balance,
-            new_total,
-        }.publish(&env);
-        
+        env.storage().instance().set(&DataKey::TimelockCount, &(op_id + 1));
+
+        let key = DataKey::TimelockOp(op_id);
+        env.storage().persistent().set(&key, &ScheduledOp {
+            op: op.clone(),
+            eta_ledger,
+        });
+        let live_for = live_until - current_ledger;
+        env.storage().persistent().extend_ttl(&key, live_for, live_for);
+
+        // 4. Emitir evento
+        OperationScheduledEvent { op_id, op, eta_ledger }.publish(&env);
+
+        Ok(op_id)
+    }
+
+    fn execute(env: Env, approvers: Vec<Address>, op_id: u32) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = Self::authorize_approvers(&env, &approvers)?;
+
+        // 2. La operación existe y su eta ya llegó
+        let scheduled = Self::scheduled_op(env.clone(), op_id)
+            .ok_or(TokenError::OperationNotFound)?;
+        if env.ledger().sequence() < scheduled.eta_ledger {
+            return Err(TokenError::TimelockNotReady);
+        }
+
+        // 3. Consumir la operación (no se puede ejecutar dos veces)
+        env.storage().persistent().remove(&DataKey::TimelockOp(op_id));
+
+        // 4. Emitir evento y aplicar
+        OperationExecutedEvent { op_id }.publish(&env);
+        Self::apply_admin_op(&env, admin, scheduled.op)
+    }
+
+    fn cancel(env: Env, approvers: Vec<Address>, op_id: u32) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        Self::authorize_approvers(&env, &approvers)?;
+
+        // 2. La operación existe
+        let key = DataKey::TimelockOp(op_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(TokenError::OperationNotFound);
+        }
+
+        // 3. Eliminar y emitir evento
+        env.storage().persistent().remove(&key);
+        OperationCancelledEvent { op_id }.publish(&env);
+
         Ok(())
     }
-    
-    fn balance(env: Env, account: Address) -> i128 {
-        env.storage().persistent()
-            .get(&DataKey::Balance(account))
-            .unwrap_or(0)
+
+    fn scheduled_op(env: Env, op_id: u32) -> Option<ScheduledOp> {
+        env.storage().persistent().get(&DataKey::TimelockOp(op_id))
     }
-    
-    fn transfer(
-        env: Env, 
-        from: Address, 
-        to: Address, 
-        amount: i128
-    ) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
+}
+
+/// Implementación del admin multisig
+#[contractimpl]
+impl MultisigTrait for TokenBDB {
+    fn set_admin_signers(env: Env, approvers: Vec<Address>, signers: SignerSet) -> Result<(), TokenError> {
+        // 1. El admin actual (una cuenta o `threshold` firmantes)
+        let admin = Self::authorize_approvers(&env, &approvers)?;
+        Self::check_schema(&env)?;
+
+        // 2. Cambiar de admin pasa por el timelock
+        Self::require_no_timelock(&env)?;
+
+        // 3. Validar, guardar y emitir evento
+        Self::apply_set_admin_signers(&env, admin, signers)
+    }
+
+    fn admin_signers(env: Env) -> Option<SignerSet> {
+        env.storage().instance().get(&DataKey::AdminSigners)
+    }
+
+    fn multisig_execute(env: Env, approvers: Vec<Address>, op: AdminOp) -> Result<(), TokenError> {
+        // 1. El admin actual (una cuenta o `threshold` firmantes)
+        let admin = Self::authorize_approvers(&env, &approvers)?;
+
+        // 2. Las operaciones sensibles siguen sujetas al timelock
+        if Self::requires_timelock(&op) {
+            Self::require_no_timelock(&env)?;
         }
-        
-        // 2. Verificar autorización del sender
-        from.require_auth();
-        
-        // 3. Validaciones
-        if amount <= 0 {
+
+        // 3. Aplicar
+        Self::apply_admin_op(&env, admin, op)
+    }
+}
+
+/// Implementación de los permits firmados
+#[contractimpl]
+impl PermitTrait for TokenBDB {
+    fn permit(
+        env: Env,
+        owner_pubkey: BytesN<32>,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+        signature: BytesN<64>
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización y circuit breaker (igual que approve)
+        Self::require_initialized(&env);
+        Self::require_transfers_enabled(&env);
+
+        // 2. Validaciones
+        if amount < 0 {
             return Err(TokenError::InvalidAmount);
         }
-        
-        // 4. No permitir transferencia a sí mismo (gas-efficient)
-        if from == to {
-            return Err(TokenError::InvalidRecipient);
+
+        // 3. El nonce debe ser el siguiente del owner (anti-replay)
+        let owner = permit::account_address(&env, &owner_pubkey);
+        if nonce != Self::permit_nonce(env.clone(), owner.clone()) {
+            return Err(TokenError::InvalidNonce);
         }
-        
-        let from_balance = Self::balance(env.clone(), from.clone());
-        if from_balance < amount {
-            return Err(TokenError::InsufficientBalance);
+        if amount > 0
+            && (Self::denylisted(env.clone(), owner.clone())
+                || Self::denylisted(env.clone(), spender.clone()))
+        {
+            return Err(TokenError::AccountDenylisted);
         }
-        
-        // 5. Calcular nuevos balances con verificación de overflow
-        let new_from_balance = from_balance - amount;
-        let to_balance = Self::balance(env.clone(), to.clone());
-        let new_to_balance = to_balance.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        
-        // 6. Actualizar balances con TTL
-        // Optimización: si from_balance = 0, eliminar key
-        if new_from_balance == 0 {
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()),
-                &new_from_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()),
-            &new_to_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()),
-            100_000,
-            200_000
+
+        // 4. Verificar firma ed25519 (aborta si es inválida)
+        let digest = permit::permit_digest(
+            &env, &owner_pubkey, &spender, amount, expiration_ledger, nonce
         );
-        
-        // 7. Emitir evento con balances post-transferencia
-        // [COM: Reemplazo del env.events().publish con el nuevo macro TransferEvent().publish()]
-        TransferEvent {
-            from: from.clone(),
-            to: to.clone(),
+        env.crypto().ed25519_verify(&owner_pubkey, &digest.into(), &signature);
+
+        // 5. Consumir nonce
+        let key = DataKey::PermitNonce(owner.clone());
+        env.storage().persistent().set(&key, &(nonce + 1));
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+
+        // 6. Escribir allowance y emitir el mismo evento que approve
+        Self::write_allowance(&env, &owner, &spender, amount, expiration_ledger);
+        ApproveEvent {
+            from: owner,
+            spender,
             amount,
-            new_from_balance,
-            new_to_balance,
+            expiration_ledger,
         }.publish(&env);
-        
+
         Ok(())
     }
-    
-    fn approve(
-        env: Env, 
-        from: Address, 
-        spender: Address, 
-        amount: i128
-    ) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
+
+    fn permit_nonce(env: Env, owner: Address) -> u64 {
+        env.storage().persistent()
+            .get(&DataKey::PermitNonce(owner))
+            .unwrap_or(0)
+    }
+
+    fn permit_digest(
+        env: Env,
+        owner_pubkey: BytesN<32>,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64
+    ) -> BytesN<32> {
+        permit::permit_digest(&env, &owner_pubkey, &spender, amount, expiration_ledger, nonce)
+    }
+}
+
+/// Implementación de la interfaz estándar SEP-41
+///
+/// Permite que DEXs, AMMs y otros contratos usen el token a través
+/// de `soroban_sdk::token::TokenClient` sin adaptadores.
+/// La interfaz no devuelve Result, así que los errores se reportan
+/// con `panic_with_error!` (el llamador sigue recibiendo TokenError)
+#[contractimpl]
+impl TokenInterface for TokenBDB {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        let value = Self::read_allowance(&env, &from, &spender);
+
+        match value {
+            // Un allowance expirado se trata como 0
+            Some(value) if value.expiration_ledger >= env.ledger().sequence() => value.amount,
+            _ => 0,
         }
-        
+    }
+
+    fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32
+    ) {
+        // 1. Verificar inicialización y circuit breaker
+        Self::require_initialized(&env);
+        Self::require_transfers_enabled(&env);
+
         // 2. Verificar autorización del owner
         from.require_auth();
-        
+
         // 3. Validación: amount debe ser >= 0 (permitir 0 para revocar)
         if amount < 0 {
-            return Err(TokenError::InvalidAmount);
+            panic_with_error!(&env, TokenError::InvalidAmount);
         }
-        
-        // 4. Obtener allowance anterior para el evento
-        let old_allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
-        
-        // 5. Actualizar allowance
-        if amount == 0 {
-            // Optimización: eliminar key si allowance = 0
-            env.storage().persistent().remove(
-                &DataKey::Allowance(from.clone(), spender.clone())
-            );
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Allowance(from.clone(), spender.clone()),
-                &amount
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Allowance(from.clone(), spender.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        // 6. Evento mejorado con allowance anterior y nuevo
-        // [COM: Reemplazo del env.events().publish con el nuevo macro ApproveEvent().publish()]
+
+        // 3b. Cuentas sancionadas no aprueban ni reciben approvals
+        // (revocar con amount = 0 siempre está permitido)
+        if amount > 0 {
+            Self::require_not_denylisted(&env, &from);
+            Self::require_not_denylisted(&env, &spender);
+        }
+
+        // 4. Actualizar allowance (eliminado si amount = 0)
+        // La expiración no puede estar en el pasado salvo al revocar
+        Self::write_allowance(&env, &from, &spender, amount, expiration_ledger);
+
+        // 5. Evento SEP-41: data = [amount, expiration_ledger]
         ApproveEvent {
             from: from.clone(),
             spender: spender.clone(),
-            old_allowance,
-            new_allowance: amount,
+            amount,
+            expiration_ledger,
         }.publish(&env);
-        
-        Ok(())
     }
-    
-    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+
+    fn balance(env: Env, id: Address) -> i128 {
         env.storage().persistent()
-            .get(&DataKey::Allowance(from, spender))
+            .get(&DataKey::Balance(id))
             .unwrap_or(0)
     }
-    
+
+    fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+        // 1. Verificar inicialización y circuit breaker
+        Self::require_initialized(&env);
+        Self::require_transfers_enabled(&env);
+
+        // 2. Verificar autorización del sender
+        from.require_auth();
+
+        // 3. Validaciones
+        if amount <= 0 {
+            panic_with_error!(&env, TokenError::InvalidAmount);
+        }
+
+        // 4. Actualizar balances (la parte muxed solo aparece en el evento);
+        // la comisión se descuenta de lo que recibe `to`
+        let to_address = to.address();
+        let fee = Self::transfer_fee(env.clone(), from.clone(), to_address.clone(), amount);
+        Self::move_balance(&env, &from, &to_address, amount - fee);
+
+        // 5. Emitir evento SEP-41
+        match to.id() {
+            Some(to_muxed_id) => TransferMuxedEvent {
+                from: from.clone(),
+                to: to_address,
+                to_muxed_id: Some(to_muxed_id),
+                amount: amount - fee,
+            }.publish(&env),
+            None => TransferEvent {
+                from: from.clone(),
+                to: to_address,
+                amount: amount - fee,
+            }.publish(&env),
+        }
+
+        // 6. Comisión a la tesorería
+        Self::pay_fee(&env, &from, fee);
+    }
+
     fn transfer_from(
-        env: Env, 
-        spender: Address, 
-        from: Address, 
-        to: Address, 
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
         amount: i128
-    ) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
-        }
-        
+    ) {
+        // 1. Verificar inicialización y circuit breaker
+        Self::require_initialized(&env);
+        Self::require_transfers_enabled(&env);
+
         // 2. Verificar autorización del spender
         spender.require_auth();
-        
+
         // 3. Validaciones
         if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
-        }
-        
-        // 4. No permitir transferencia a sí mismo
-        if from == to {
-            return Err(TokenError::InvalidRecipient);
-        }
-        
-        // 5. Verificar allowance
-        let allowed = Self::allowance(env.clone(), from.clone(), spender.clone());
-        if allowed < amount {
-            return Err(TokenError::InsufficientAllowance);
+            panic_with_error!(&env, TokenError::InvalidAmount);
         }
-        
-        // 6. Verificar balance
-        let from_balance = Self::balance(env.clone(), from.clone());
-        if from_balance < amount {
-            return Err(TokenError::InsufficientBalance);
-        }
-        
-        // 7. Calcular nuevos valores
-        let new_from_balance = from_balance - amount;
-        let to_balance = Self::balance(env.clone(), to.clone());
-        let new_to_balance = to_balance.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        let new_allowance = allowed - amount;
-        
-        // 8. Actualizar estado atómicamente
-        // Optimización: eliminar keys si son 0
-        if new_from_balance == 0 {
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()),
-                &new_from_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()),
-            &new_to_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()),
-            100_000,
-            200_000
-        );
-        
-        if new_allowance == 0 {
-            env.storage().persistent().remove(
-                &DataKey::Allowance(from.clone(), spender.clone())
-            );
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Allowance(from.clone(), spender.clone()),
-                &new_allowance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Allowance(from.clone(), spender.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        // 9. Emitir evento completo (FIX: evento faltante)
-        // [COM: Reemplazo del env.events().publish con el nuevo macro TransferEvent().publish(). Nota: TransferFrom usa el mismo TransferEvent según el estándar CAP-46.]
+
+        // 4. Consumir allowance y mover balances (atómico: cualquier
+        // panic revierte todos los cambios)
+        Self::require_not_denylisted(&env, &spender);
+        // La comisión se descuenta de lo que recibe `to`
+        Self::spend_allowance(&env, &from, &spender, amount);
+        let fee = Self::transfer_fee(env.clone(), from.clone(), to.clone(), amount);
+        Self::move_balance(&env, &from, &to, amount - fee);
+
+        // 5. Emitir evento (transfer_from usa el mismo TransferEvent)
         TransferEvent {
             from: from.clone(),
             to: to.clone(),
-            amount,
-            new_from_balance,
-            new_to_balance,
+            amount: amount - fee,
         }.publish(&env);
-        
-        Ok(())
+
+        // 6. Comisión a la tesorería
+        Self::pay_fee(&env, &from, fee);
     }
-    
-    // Métodos de consulta
-    fn name(env: Env) -> String {
-        // Verificar inicialización antes de devolver metadata
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return String::from_str(&env, "");
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        // 1. Verificar inicialización y circuit breaker
+        Self::require_initialized(&env);
+        Self::require_transfers_enabled(&env);
+
+        // 2. Requiere autorización del dueño de los tokens
+        from.require_auth();
+
+        // 3. Validaciones
+        if amount <= 0 {
+            panic_with_error!(&env, TokenError::InvalidAmount);
         }
-        
-        env.storage().instance()
-            .get(&DataKey::TokenName)
-            .unwrap_or(String::from_str(&env, ""))
+
+        // 4. Actualizar balance y total supply (cuentas congeladas o
+        // sancionadas no queman)
+        Self::require_authorized(&env, &from);
+        Self::require_not_denylisted(&env, &from);
+        Self::burn_balance(&env, &from, amount);
+
+        // 5. Emitir evento
+        BurnEvent {
+            from: from.clone(),
+            amount,
+        }.publish(&env);
     }
-    
-    fn symbol(env: Env) -> String {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return String::from_str(&env, "");
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        // 1. Verificar inicialización y circuit breaker
+        Self::require_initialized(&env);
+        Self::require_transfers_enabled(&env);
+
+        // 2. Verificar autorización del spender
+        spender.require_auth();
+
+        // 3. Validaciones
+        if amount <= 0 {
+            panic_with_error!(&env, TokenError::InvalidAmount);
         }
-        
-        env.storage().instance()
-            .get(&DataKey::TokenSymbol)
-            .unwrap_or(String::from_str(&env, ""))
+
+        // 4. Consumir allowance y quemar (cuentas congeladas o
+        // sancionadas no queman)
+        Self::require_authorized(&env, &from);
+        Self::require_not_denylisted(&env, &from);
+        Self::require_not_denylisted(&env, &spender);
+        Self::spend_allowance(&env, &from, &spender, amount);
+        Self::burn_balance(&env, &from, amount);
+
+        // 5. Emitir evento
+        BurnEvent {
+            from: from.clone(),
+            amount,
+        }.publish(&env);
     }
-    
+
+    // Métodos de consulta
+    // SEP-41: los metadatos no existen antes de initialize()
     fn decimals(env: Env) -> u32 {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return 0;
-        }
-        
+        Self::require_initialized(&env);
+
         env.storage().instance()
             .get(&DataKey::Decimals)
-            .unwrap_or(0)
+            .unwrap()
     }
-    
-    fn total_supply(env: Env) -> i128 {
+
+    fn name(env: Env) -> String {
+        Self::require_initialized(&env);
+
         env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0)
+            .get(&DataKey::TokenName)
+            .unwrap()
     }
-    
-    fn admin(env: Env) -> Address {
+
+    fn symbol(env: Env) -> String {
+        Self::require_initialized(&env);
+
         env.storage().instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not initialized")
+            .get(&DataKey::TokenSymbol)
+            .unwrap()
     }
 }
 
 // ✨ Tests ✨
 #[cfg(test)]
-mod test;
\ No newline at end of file
+mod test;
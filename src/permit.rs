@@ -0,0 +1,93 @@
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+/// Alfabeto base32 (RFC 4648) usado por las strkeys de Stellar
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Version byte de una strkey de cuenta (G...): 6 << 3
+const ACCOUNT_ID_VERSION_BYTE: u8 = 6 << 3;
+
+/// Mensaje que el owner firma off-chain para un permit
+///
+/// Incluye el contrato y el network id (hash de la network passphrase)
+/// para que una firma no se pueda reutilizar en otro token ni en otra red,
+/// y un nonce por owner para que no se pueda reutilizar en este mismo token
+#[contracttype]
+#[derive(Clone)]
+pub struct PermitPayload {
+    pub domain: Symbol,
+    pub contract: Address,
+    pub network_id: BytesN<32>,
+    pub owner: BytesN<32>,
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+    pub nonce: u64,
+}
+
+/// Hash sha256 del XDR del PermitPayload: esto es lo que firma el owner
+pub fn permit_digest(
+    env: &Env,
+    owner_pubkey: &BytesN<32>,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u64
+) -> BytesN<32> {
+    let payload = PermitPayload {
+        domain: symbol_short!("permit"),
+        contract: env.current_contract_address(),
+        network_id: env.ledger().network_id(),
+        owner: owner_pubkey.clone(),
+        spender: spender.clone(),
+        amount,
+        expiration_ledger,
+        nonce,
+    };
+
+    env.crypto().sha256(&payload.to_xdr(env)).into()
+}
+
+/// Dirección de cuenta (G...) correspondiente a una clave pública ed25519
+///
+/// Codifica la strkey a mano (version byte + clave + CRC16-XModem, en
+/// base32) porque el SDK solo construye Address a partir de strkeys
+pub fn account_address(env: &Env, pubkey: &BytesN<32>) -> Address {
+    let mut raw = [0u8; 35];
+    raw[0] = ACCOUNT_ID_VERSION_BYTE;
+    raw[1..33].copy_from_slice(&pubkey.to_array());
+    let checksum = crc16_xmodem(&raw[..33]);
+    raw[33..].copy_from_slice(&checksum.to_le_bytes());
+
+    // 35 bytes = 280 bits = 56 caracteres base32 exactos (sin padding)
+    let mut strkey = [0u8; 56];
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    let mut out = 0;
+    for byte in raw {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            strkey[out] = BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize];
+            out += 1;
+        }
+    }
+
+    Address::from_string_bytes(&Bytes::from_array(env, &strkey))
+}
+
+/// CRC16-XModem (polinomio 0x1021), el checksum de las strkeys
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 /// Enum que define todas las claves de almacenamiento
 /// 
@@ -13,16 +13,111 @@ pub enum DataKey {
     
     /// Permisos de gasto entre usuarios - Persistent Storage
     /// Tupla (owner, spender) para lookup eficiente
+    /// Guarda un AllowanceValue (amount + expiration_ledger)
+    /// El TTL de la entrada se ajusta a expiration_ledger
     Allowance(Address, Address),
     
     /// Supply total de tokens - Instance Storage
     /// Contador global de tokens en circulación
     TotalSupply,
     
+    /// Tope de emisión opcional - Instance Storage
+    /// Solo se puede fijar una vez y después bajar
+    MaxSupply,
+    
+    /// Máximo de partidas por lote - Instance Storage
+    /// Ausente = DEFAULT_MAX_BATCH_SIZE
+    MaxBatchSize,
+    
     /// Dirección del administrador - Instance Storage
     /// Solo esta cuenta puede mintear tokens
+    /// Se elimina con renounce_admin()
     Admin,
     
+    /// Firmantes del admin multisig - Instance Storage
+    /// Ver SignerSet; mientras existe, Admin es el propio contrato
+    AdminSigners,
+    
+    /// Admin propuesto pendiente de aceptar - Instance Storage
+    /// Transferencia de admin en dos pasos (propose/accept)
+    PendingAdmin,
+    
+    /// Rol otorgado a una cuenta - Persistent Storage
+    /// Tupla (account, role); la key solo existe si el rol está otorgado
+    Role(Address, Role),
+    
+    /// Próximo nonce de permit de cada owner - Persistent Storage
+    /// Evita reutilizar una firma de permit()
+    PermitNonce(Address),
+    
+    /// Cuenta congelada por el admin - Persistent Storage
+    /// La key solo existe mientras la cuenta está congelada
+    Frozen(Address),
+    
+    /// Cuenta aprobada en la allowlist - Persistent Storage
+    /// La key solo existe mientras la cuenta está aprobada
+    Allowlisted(Address),
+    
+    /// Cuenta sancionada (denylist) - Persistent Storage
+    /// La key solo existe mientras la cuenta está sancionada
+    Denylisted(Address),
+    
+    /// Cupo de minteo de cada minter - Persistent Storage
+    /// Ver MinterQuota; el admin no tiene cupo
+    MinterQuota(Address),
+    
+    /// Airdrop Merkle en curso - Instance Storage
+    /// Ver Airdrop; se elimina con sweep_airdrop()
+    Airdrop,
+    
+    /// Número de airdrops creados - Instance Storage
+    /// Id del próximo airdrop (separa los bitmaps de cada ronda)
+    AirdropCount,
+    
+    /// Palabra del bitmap de claims - Persistent Storage
+    /// Tupla (airdrop_id, index / 64); bit index % 64 = ya reclamado
+    AirdropClaimed(u32, u32),
+    
+    /// Calendario de vesting - Persistent Storage
+    /// Ver VestingSchedule; el id lo asigna create_vesting()
+    Vesting(u32),
+    
+    /// Número de calendarios creados - Instance Storage
+    /// Id del próximo calendario de vesting
+    VestingCount,
+    
+    /// Último valor del historial de balances de una cuenta - Persistent Storage
+    /// Ver History
+    BalanceHistory(Address),
+    
+    /// Valores anteriores del historial de una cuenta - Persistent Storage
+    /// Tupla (account, index); ver Checkpoint
+    BalanceCheckpoint(Address, u32),
+    
+    /// Último valor del historial del supply total - Persistent Storage
+    /// Ver History
+    SupplyHistory,
+    
+    /// Valores anteriores del historial del supply - Persistent Storage
+    /// Indexado por posición; ver Checkpoint
+    SupplyCheckpoint(u32),
+    
+    /// Delegado de votos de una cuenta - Persistent Storage
+    /// La key solo existe si la cuenta delegó (también en sí misma)
+    Delegate(Address),
+    
+    /// Último valor del historial de votos de un delegado - Persistent Storage
+    /// Ver History
+    VotesHistory(Address),
+    
+    /// Valores anteriores del historial de votos - Persistent Storage
+    /// Tupla (delegatee, index); ver Checkpoint
+    VotesCheckpoint(Address, u32),
+    
+    /// Política de comisión por transferencia - Instance Storage
+    /// Ver FeePolicy; ausente = sin comisión
+    FeePolicy,
+    
     /// Nombre del token - Instance Storage
     /// Ejemplo: "Buen Día Builders Token"
     TokenName,
@@ -35,15 +130,262 @@ pub enum DataKey {
     /// Típicamente 7 para Stellar (alineado con XLM)
     Decimals,
     
+    /// Si el token permite clawback - Instance Storage
+    /// Se fija en initialize() y no cambia nunca
+    ClawbackEnabled,
+    
+    /// Si el token funciona en modo allowlist - Instance Storage
+    /// Se fija en initialize() y no cambia nunca
+    AllowlistEnabled,
+    
+    /// Estado del circuit breaker - Instance Storage
+    /// Ver PauseState; ausente = nada pausado
+    Paused,
+    
+    /// Delay del timelock en ledgers - Instance Storage
+    /// Ausente = 0 (sin timelock)
+    TimelockDelay,
+    
+    /// Número de operaciones programadas - Instance Storage
+    /// Id de la próxima operación de schedule()
+    TimelockCount,
+    
+    /// Operación programada - Persistent Storage
+    /// Ver ScheduledOp; se elimina al ejecutarla o cancelarla
+    TimelockOp(u32),
+    
+    /// Versión del esquema de storage - Instance Storage
+    /// Ver STORAGE_VERSION; ausente = esquema 1 (antes de migrate())
+    StorageVersion,
+    
+    /// Versión del código del contrato - Instance Storage
+    /// 1 al inicializar, upgrade() la incrementa
+    Version,
+    
     /// Flag para verificar inicialización - Instance Storage
     /// Previene re-inicialización del contrato
     Initialized,
 }
 
-/// Metadata struct para almacenar información del token
-/// Usado en initialize() para pasar múltiples parámetros
+/// Roles del control de acceso
+///
+/// Cada rol habilita un grupo de funciones privilegiadas.
+/// El admin tiene todos los roles implícitamente
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[allow(clippy::enum_variant_names)] // RoleAdmin: nombre estándar del rol
+pub enum Role {
+    /// Puede crear tokens con mint()
+    Minter,
+    /// Puede destruir tokens de otras cuentas (clawback)
+    Burner,
+    /// Puede pausar y reanudar el token
+    Pauser,
+    /// Puede actualizar nombre y símbolo
+    MetadataManager,
+    /// Puede otorgar y revocar roles
+    RoleAdmin,
+    /// Puede agregar y quitar cuentas de la allowlist
+    ComplianceManager,
+    /// Puede agregar y quitar cuentas de la denylist (sanciones)
+    SanctionsManager,
+}
+
+/// Grupo de operaciones afectado por pause()/unpause()
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PauseScope {
+    /// Transfers y Minting
+    All,
+    /// transfer, transfer_from, approve, burn y burn_from
+    Transfers,
+    /// mint
+    Minting,
+}
+
+/// Estado guardado en DataKey::Paused
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseState {
+    pub transfers: bool,
+    pub minting: bool,
+}
+
+/// Cupo de minteo guardado en DataKey::MinterQuota
+///
+/// Funciona como un allowance de minteo: mint() descuenta de
+/// `remaining` y, si `window_ledgers` > 0, tampoco permite superar
+/// `window_limit` dentro de cada ventana de ledgers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinterQuota {
+    pub remaining: i128,
+    pub window_ledgers: u32,
+    pub window_limit: i128,
+    pub window_start: u32,
+    pub window_minted: i128,
+}
+
+/// Airdrop guardado en DataKey::Airdrop
+///
+/// La reserva (`remaining`) vive en el balance del propio contrato:
+/// claim() la reparte y sweep_airdrop() devuelve lo no reclamado
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Airdrop {
+    pub id: u32,
+    pub merkle_root: BytesN<32>,
+    pub deadline_ledger: u32,
+    pub remaining: i128,
+}
+
+/// Calendario guardado en DataKey::Vesting
+///
+/// Los tokens bloqueados viven en el balance del propio contrato
+/// hasta que release() los paga al beneficiario
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub beneficiary: Address,
+    pub total: i128,
+    pub released: i128,
+    pub start_ledger: u32,
+    pub cliff_ledger: u32,
+    pub end_ledger: u32,
+    pub revocable: bool,
+    pub revoked: bool,
+}
+
+/// Entrada de un historial (BalanceCheckpoint, SupplyCheckpoint)
+///
+/// `value` es el valor vigente desde `ledger` hasta la entrada siguiente
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub value: i128,
+}
+
+/// Cabeza de un historial (BalanceHistory, SupplyHistory)
+///
+/// Guarda el valor vigente desde `ledger` y cuántas entradas
+/// anteriores (Checkpoint) hay en los índices 0..count
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct History {
+    pub count: u32,
+    pub ledger: u32,
+    pub value: i128,
+}
+
+/// Política guardada en DataKey::FeePolicy
+///
+/// La comisión es `bps` basis points del monto, acotada a
+/// [`min_fee`, `max_fee`], y se descuenta de lo que recibe el
+/// destinatario. Las transferencias desde/hacia `treasury` o una
+/// cuenta de `exempt` no pagan comisión
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeePolicy {
+    pub bps: u32,
+    pub min_fee: i128,
+    pub max_fee: i128,
+    pub treasury: Address,
+    pub exempt: Vec<Address>,
+}
+
+/// Firmantes guardados en DataKey::AdminSigners
+///
+/// Las operaciones de admin requieren la autorización de al
+/// menos `threshold` cuentas distintas de `signers`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerSet {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Operación administrativa (timelock y admin multisig)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminOp {
+    /// upgrade(new_wasm_hash)
+    Upgrade(BytesN<32>),
+    /// propose_admin(new_admin)
+    ProposeAdmin(Address),
+    /// set_fee_policy(Some(policy))
+    SetFeePolicy(FeePolicy),
+    /// set_fee_policy(None)
+    RemoveFeePolicy,
+    /// set_max_supply(max_supply)
+    SetMaxSupply(i128),
+    /// unpause(scope)
+    Unpause(PauseScope),
+    /// Cambia el propio delay del timelock
+    SetTimelockDelay(u32),
+    /// set_admin_signers(signers)
+    SetAdminSigners(SignerSet),
+    /// mint(admin, to, amount)
+    Mint(Address, i128),
+    /// grant_role(admin, account, role)
+    GrantRole(Address, Role),
+    /// revoke_role(admin, account, role)
+    RevokeRole(Address, Role),
+    /// migrate(allowances, complete)
+    Migrate(Vec<(Address, Address)>, bool),
+    /// set_minter_quota(minter, total, window_ledgers, window_limit)
+    SetMinterQuota(Address, i128, u32, i128),
+    /// set_authorized(id, authorize)
+    SetAuthorized(Address, bool),
+    /// set_max_batch_size(max_batch_size)
+    SetMaxBatchSize(u32),
+    /// start_airdrop(merkle_root, amount, deadline_ledger)
+    StartAirdrop(BytesN<32>, i128, u32),
+    /// sweep_airdrop(to)
+    SweepAirdrop(Address),
+    /// create_vesting(beneficiary, total, start, cliff, end, revocable)
+    CreateVesting(Address, i128, u32, u32, u32, bool),
+    /// revoke_vesting(id, to)
+    RevokeVesting(u32, Address),
+    /// renounce_admin()
+    RenounceAdmin,
+}
+
+/// Operación guardada en DataKey::TimelockOp
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledOp {
+    pub op: AdminOp,
+    pub eta_ledger: u32,
+}
+
+/// Valor guardado en DataKey::Allowance (desde el esquema 2)
+///
+/// SEP-41: el allowance vale 0 una vez que el ledger actual
+/// supera `expiration_ledger`
 #[contracttype]
 #[derive(Clone)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Opciones de initialize()
+///
+/// `max_supply` = None deja el token sin tope; `clawback_enabled`
+/// y `allowlist_enabled` no cambian nunca después de inicializar
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TokenConfig {
+    pub max_supply: Option<i128>,
+    pub clawback_enabled: bool,
+    pub allowlist_enabled: bool,
+}
+
+/// Metadata del token (TokenName, TokenSymbol, Decimals)
+/// Usado por set_metadata() y metadata()
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TokenMetadata {
     pub name: String,
     pub symbol: String,
@@ -1,10 +1,22 @@
 #![cfg(test)]
+extern crate std;
 
-use super::*; 
+use super::*;
+use crate::storage::History;
 use soroban_sdk::{
-    testutils::Address as _,
-    Address, Env, String,
+    contract, contractimpl, symbol_short, token, vec,
+    testutils::{
+        storage::Persistent as _, Address as _, AuthorizedFunction, AuthorizedInvocation,
+        Events, Ledger, MockAuth, MockAuthInvoke, MuxedAddress as _,
+    },
+    xdr::{AccountId, PublicKey, ScAddress, Uint256},
+    Address, Bytes, BytesN, Env, IntoVal, Map, MuxedAddress, String, Symbol, TryFromVal, Val,
 };
+use ed25519_dalek::{Signer, SigningKey};
+
+/// Ledger de expiración usado en los approve() de los tests
+/// (el ledger inicial del entorno de test es 0)
+const EXPIRATION_LEDGER: u32 = 1_000;
 
 /// Test básico de inicialización del token
 /// 
@@ -24,7 +36,7 @@ fn test_initialize() {
     let symbol = String::from_str(&env, "BDB");
     
     // Act: Inicializar el token
-    client.initialize(&admin, &name, &symbol, &7);  // Retirar .unwrap()
+    client.initialize(&admin, &name, &symbol, &7, &TokenConfig::default());  // Retirar .unwrap()
     
     // Assert: Verificar que los metadatos se guardaron correctamente
     assert_eq!(client.name(), name);
@@ -48,10 +60,10 @@ fn test_initialize_twice_fails() {
     let symbol = String::from_str(&env, "TOK");
     
     // Primera inicialización debe funcionar
-    client.initialize(&admin, &name, &symbol, &7);  // Retirar .unwrap()
+    client.initialize(&admin, &name, &symbol, &7, &TokenConfig::default());  // Retirar .unwrap()
     
     // Segunda debe fallar con AlreadyInitialized
-    let result = client.try_initialize(&admin, &name, &symbol, &7);  // try_
+    let result = client.try_initialize(&admin, &name, &symbol, &7, &TokenConfig::default());  // try_
     assert_eq!(result, Err(Ok(TokenError::AlreadyInitialized)));
 }
 
@@ -73,7 +85,8 @@ fn test_invalid_decimals() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &19  // ❌ Inválido: excede MAX_DECIMALS (18)
+        &19,  // ❌ Inválido: excede MAX_DECIMALS (18)
+        &TokenConfig::default()
     );
     assert_eq!(result, Err(Ok(TokenError::InvalidDecimals)));
 }
@@ -99,14 +112,15 @@ fn test_mint_and_balance() {
         &admin, 
         &String::from_str(&env, "Builder Token"),
         &String::from_str(&env, "BDB"),
-        &7
+        &7,
+        &TokenConfig::default()
     );  // Retirar .unwrap()
     
     // Mock auth: En tests, simulamos autorizaciones sin firmas reales
     env.mock_all_auths();
     
     // Mintear 1000 tokens
-    client.mint(&user, &1000);  // Retirar .unwrap()
+    client.mint(&admin, &user, &1000);  // Retirar .unwrap()
     
     // Verificar estado actualizado
     assert_eq!(client.balance(&user), 1000);
@@ -130,13 +144,14 @@ fn test_mint_zero_fails() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );  // // Retirar .unwrap()
     
     env.mock_all_auths();
     
     // Mintear 0 debe fallar con InvalidAmount
-    let result = client.try_mint(&user, &0);  // Retirar .unwrap()
+    let result = client.try_mint(&admin, &user, &0);  // Retirar .unwrap()
     assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
 }
 
@@ -161,11 +176,12 @@ fn test_transfer() {
         &admin,
         &String::from_str(&env, "Builder Token"),
         &String::from_str(&env, "BDB"),
-        &7
+        &7,
+        &TokenConfig::default()
     );  // Retirar .unwrap()
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);  // Retirar .unwrap()
+    client.mint(&admin, &alice, &1000);  // Retirar .unwrap()
     
     // Act: Alice transfiere a Bob
     client.transfer(&alice, &bob, &250);  // Retirar .unwrap()
@@ -193,15 +209,16 @@ fn test_transfer_insufficient_balance() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );  // Retirar .unwrap()
     
     env.mock_all_auths();
-    client.mint(&alice, &100);  // Retirar .unwrap()
+    client.mint(&admin, &alice, &100);  // Retirar .unwrap()
     
     // Intentar transferir más de lo que tiene debe fallar
     let result = client.try_transfer(&alice, &bob, &200);  // try_
-    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance)));
+    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance.into())));
 }
 
 /// Test: transfer a sí mismo debe fallar
@@ -223,15 +240,16 @@ fn test_transfer_to_self() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );  // Retirar .unwrap()
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);  // Retirar .unwrap()
+    client.mint(&admin, &alice, &1000);  // Retirar .unwrap()
     
     // Transfer a sí mismo debe fallar con InvalidRecipient
     let result = client.try_transfer(&alice, &alice, &100);  // try
-    assert_eq!(result, Err(Ok(TokenError::InvalidRecipient)));
+    assert_eq!(result, Err(Ok(TokenError::InvalidRecipient.into())));
     assert_eq!(client.balance(&alice), 1000); // Balance no debe cambiar
 }
 
@@ -257,14 +275,15 @@ fn test_approve_and_transfer_from() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );  // Retirar .unwrap()
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);  // Retirar .unwrap()
+    client.mint(&admin, &alice, &1000);  // Retirar .unwrap()
     
     // Alice aprueba a Bob para gastar hasta 300 tokens
-    client.approve(&alice, &bob, &300);  // Retirar .unwrap()
+    client.approve(&alice, &bob, &300, &EXPIRATION_LEDGER);  // Retirar .unwrap()
     assert_eq!(client.allowance(&alice, &bob), 300);
     
     // Bob transfiere 200 tokens de Alice a Charlie
@@ -294,16 +313,17 @@ fn test_transfer_from_insufficient_allowance() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );  // Retirar .unwrap()
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);  // Retirar .unwrap()
-    client.approve(&alice, &bob, &100);  // // Retirar .unwrap()
+    client.mint(&admin, &alice, &1000);  // Retirar .unwrap()
+    client.approve(&alice, &bob, &100, &EXPIRATION_LEDGER);  // // Retirar .unwrap()
     
     // Bob intenta transferir más de lo aprobado
     let result = client.try_transfer_from(&bob, &alice, &charlie, &200);  // // try
-    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance)));
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
 }
 
 /// Test básico de burn (quemar tokens)
@@ -323,11 +343,12 @@ fn test_burn() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );  // // Retirar .unwrap()
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);  // // Retirar .unwrap()
+    client.mint(&admin, &alice, &1000);  // // Retirar .unwrap()
     
     // Alice quema 300 de sus tokens
     client.burn(&alice, &300);  // // Retirar .unwrap()
@@ -353,18 +374,18 @@ fn test_operations_without_init() {
     
     // Todas las operaciones deben fallar con NotInitialized
     assert_eq!(
-        client.try_mint(&alice, &100),  // // try
+        client.try_mint(&bob, &alice, &100),  // // try
         Err(Ok(TokenError::NotInitialized))
     );
     
     assert_eq!(
         client.try_transfer(&alice, &bob, &50),  // // try
-        Err(Ok(TokenError::NotInitialized))
+        Err(Ok(TokenError::NotInitialized.into()))
     );
     
     assert_eq!(
         client.try_burn(&alice, &10),  // // try
-        Err(Ok(TokenError::NotInitialized))
+        Err(Ok(TokenError::NotInitialized.into()))
     );
 }
 
@@ -386,14 +407,15 @@ fn test_mint_large_amount() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
     
     // Mintear cantidad muy grande (pero segura)
     let large_amount = 1_000_000_000_000i128; // 1 trillón
-    client.mint(&user, &large_amount);
+    client.mint(&admin, &user, &large_amount);
     
     assert_eq!(client.balance(&user), large_amount);
     assert_eq!(client.total_supply(), large_amount);
@@ -413,17 +435,18 @@ fn test_mint_overflow_prevention() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
     
     // Mintear casi el máximo
     let almost_max = i128::MAX - 1000;
-    client.mint(&user, &almost_max);
+    client.mint(&admin, &user, &almost_max);
     
     // Intentar mintear más debería fallar con OverflowError
-    let result = client.try_mint(&user, &2000);
+    let result = client.try_mint(&admin, &user, &2000);
     assert_eq!(result, Err(Ok(TokenError::OverflowError)));
 }
 
@@ -442,11 +465,12 @@ fn test_balance_zero_after_full_transfer() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
+    client.mint(&admin, &alice, &1000);
     
     // Alice transfiere TODO su balance
     client.transfer(&alice, &bob, &1000);
@@ -470,11 +494,12 @@ fn test_burn_entire_balance() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
+    client.mint(&admin, &alice, &1000);
     
     // Quemar TODO el balance
     client.burn(&alice, &1000);
@@ -500,14 +525,15 @@ fn test_allowance_exhausted() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
+    client.mint(&admin, &alice, &1000);
     
     // Alice aprueba 500 a Bob
-    client.approve(&alice, &bob, &500);
+    client.approve(&alice, &bob, &500, &EXPIRATION_LEDGER);
     
     // Bob usa TODA la allowance
     client.transfer_from(&bob, &alice, &charlie, &500);
@@ -517,7 +543,7 @@ fn test_allowance_exhausted() {
     
     // Bob no puede transferir más
     let result = client.try_transfer_from(&bob, &alice, &charlie, &1);
-    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance)));
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
 }
 
 /// Revocar allowance estableciendo a 0
@@ -535,17 +561,18 @@ fn test_revoke_allowance() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
     
     // Alice aprueba a Bob
-    client.approve(&alice, &bob, &1000);
+    client.approve(&alice, &bob, &1000, &EXPIRATION_LEDGER);
     assert_eq!(client.allowance(&alice, &bob), 1000);
     
     // Alice revoca la aprobación
-    client.approve(&alice, &bob, &0);
+    client.approve(&alice, &bob, &0, &EXPIRATION_LEDGER);
     assert_eq!(client.allowance(&alice, &bob), 0);
 }
 
@@ -563,15 +590,16 @@ fn test_multiple_mints_accumulate() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
     
     // Múltiples mints
-    client.mint(&user, &100);
-    client.mint(&user, &200);
-    client.mint(&user, &300);
+    client.mint(&admin, &user, &100);
+    client.mint(&admin, &user, &200);
+    client.mint(&admin, &user, &300);
     
     // Balance debe ser la suma
     assert_eq!(client.balance(&user), 600);
@@ -590,7 +618,7 @@ fn test_empty_name_fails() {
     let symbol = String::from_str(&env, "TOK");
     
     // Debe fallar con InvalidMetadata
-    let result = client.try_initialize(&admin, &empty_name, &symbol, &7);
+    let result = client.try_initialize(&admin, &empty_name, &symbol, &7, &TokenConfig::default());
     assert_eq!(result, Err(Ok(TokenError::InvalidMetadata)));
 }
 
@@ -606,7 +634,7 @@ fn test_empty_symbol_fails() {
     let empty_symbol = String::from_str(&env, "");
     
     // Debe fallar con InvalidMetadata
-    let result = client.try_initialize(&admin, &name, &empty_symbol, &7);
+    let result = client.try_initialize(&admin, &name, &empty_symbol, &7, &TokenConfig::default());
     assert_eq!(result, Err(Ok(TokenError::InvalidMetadata)));
 }
 
@@ -630,15 +658,16 @@ fn test_multiple_users_balances() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
     
     // Mintear a múltiples usuarios
-    client.mint(&user1, &100);
-    client.mint(&user2, &200);
-    client.mint(&user3, &300);
+    client.mint(&admin, &user1, &100);
+    client.mint(&admin, &user2, &200);
+    client.mint(&admin, &user3, &300);
     
     // Verificar balances individuales
     assert_eq!(client.balance(&user1), 100);
@@ -663,11 +692,12 @@ fn test_chain_transfers() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
+    client.mint(&admin, &alice, &1000);
     
     // Alice -> Bob -> Charlie
     client.transfer(&alice, &bob, &500);
@@ -694,14 +724,15 @@ fn test_multiple_approvals() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
     
     // Alice aprueba a Bob y Charlie
-    client.approve(&alice, &bob, &100);
-    client.approve(&alice, &charlie, &200);
+    client.approve(&alice, &bob, &100, &EXPIRATION_LEDGER);
+    client.approve(&alice, &charlie, &200, &EXPIRATION_LEDGER);
     
     // Verificar allowances independientes
     assert_eq!(client.allowance(&alice, &bob), 100);
@@ -726,7 +757,8 @@ fn test_balance_of_empty_account() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     // Balance de cuenta sin tokens debe ser 0
@@ -748,7 +780,8 @@ fn test_allowance_without_approval() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     // Allowance sin approve previo debe ser 0
@@ -770,7 +803,8 @@ fn test_total_supply_increments() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
@@ -778,10 +812,10 @@ fn test_total_supply_increments() {
     // Supply inicial es 0
     assert_eq!(client.total_supply(), 0);
     
-    client.mint(&user1, &500);
+    client.mint(&admin, &user1, &500);
     assert_eq!(client.total_supply(), 500);
     
-    client.mint(&user2, &300);
+    client.mint(&admin, &user2, &300);
     assert_eq!(client.total_supply(), 800);
 }
 
@@ -799,11 +833,12 @@ fn test_total_supply_decrements_on_burn() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
+    client.mint(&admin, &alice, &1000);
     assert_eq!(client.total_supply(), 1000);
     
     client.burn(&alice, &300);
@@ -827,7 +862,8 @@ fn test_decimals_configuration() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &18  // Máximo permitido
+        &18,  // Máximo permitido
+        &TokenConfig::default()
     );
     
     assert_eq!(client.decimals(), 18);
@@ -846,7 +882,8 @@ fn test_admin_address_stored() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     // Admin debe coincidir con el configurado
@@ -872,11 +909,12 @@ fn test_transfer_exact_balance() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &999);
+    client.mint(&admin, &alice, &999);
     
     // Transferir exactamente el balance
     client.transfer(&alice, &bob, &999);
@@ -901,16 +939,3852 @@ fn test_use_exact_allowance() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
+        &7,
+        &TokenConfig::default()
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
-    client.approve(&alice, &bob, &456);
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &456, &EXPIRATION_LEDGER);
     
     // Usar exactamente toda la allowance
     client.transfer_from(&bob, &alice, &charlie, &456);
     
     assert_eq!(client.allowance(&alice, &bob), 0);
     assert_eq!(client.balance(&charlie), 456);
-}
\ No newline at end of file
+}
+// ============================================================================
+// TESTS DE COMPATIBILIDAD SEP-41
+// ============================================================================
+
+/// Contrato mínimo que usa el token a través del TokenClient estándar
+///
+/// Simula un DEX/AMM que mueve y quema tokens de los usuarios
+/// después de que éstos le aprueban un allowance
+#[contract]
+pub struct SpenderContract;
+
+#[contractimpl]
+impl SpenderContract {
+    pub fn pull(env: Env, token: Address, from: Address, to: Address, amount: i128) {
+        let client = token::TokenClient::new(&env, &token);
+        client.transfer_from(&env.current_contract_address(), &from, &to, &amount);
+    }
+
+    pub fn redeem(env: Env, token: Address, from: Address, amount: i128) {
+        let client = token::TokenClient::new(&env, &token);
+        client.burn_from(&env.current_contract_address(), &from, &amount);
+    }
+}
+
+/// El token se puede operar completamente con el TokenClient del SDK
+#[test]
+fn test_sep41_token_client() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    let token = token::TokenClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Builder Token"),
+        &String::from_str(&env, "BDB"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    // Metadatos vía la interfaz estándar
+    assert_eq!(token.name(), String::from_str(&env, "Builder Token"));
+    assert_eq!(token.symbol(), String::from_str(&env, "BDB"));
+    assert_eq!(token.decimals(), 7);
+    
+    // transfer / approve / transfer_from / burn / burn_from
+    token.transfer(&alice, &bob, &100);
+    token.approve(&alice, &bob, &300, &EXPIRATION_LEDGER);
+    token.transfer_from(&bob, &alice, &charlie, &200);
+    token.burn(&bob, &50);
+    token.burn_from(&bob, &alice, &100);
+    
+    assert_eq!(token.balance(&alice), 600);
+    assert_eq!(token.balance(&bob), 50);
+    assert_eq!(token.balance(&charlie), 200);
+    assert_eq!(token.allowance(&alice, &bob), 0);
+    assert_eq!(client.total_supply(), 850);
+}
+
+/// Otro contrato puede gastar y quemar tokens aprobados (cross-contract)
+#[test]
+fn test_sep41_cross_contract_spender() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    let spender_id = env.register(SpenderContract, ());
+    let spender = SpenderContractClient::new(&env, &spender_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &spender_id, &500, &EXPIRATION_LEDGER);
+    
+    // El contrato mueve 300 a Bob y quema 100 usando su allowance
+    spender.pull(&contract_id, &alice, &bob, &300);
+    spender.redeem(&contract_id, &alice, &100);
+    
+    assert_eq!(client.balance(&alice), 600);
+    assert_eq!(client.balance(&bob), 300);
+    assert_eq!(client.allowance(&alice, &spender_id), 100);
+    assert_eq!(client.total_supply(), 900);
+}
+
+/// Los eventos siguen los topics/data definidos en SEP-41
+#[test]
+fn test_sep41_event_shapes() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    client.mint(&admin, &alice, &1000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), alice.clone()).into_val(&env),
+                1000i128.into_val(&env),
+            ),
+        ]
+    );
+    
+    client.approve(&alice, &bob, &300, &EXPIRATION_LEDGER);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("approve"), alice.clone(), bob.clone()).into_val(&env),
+                (300i128, EXPIRATION_LEDGER).into_val(&env),
+            ),
+        ]
+    );
+    
+    client.transfer(&alice, &bob, &250);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env),
+                250i128.into_val(&env),
+            ),
+        ]
+    );
+    
+    client.burn(&bob, &50);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("burn"), bob.clone()).into_val(&env),
+                50i128.into_val(&env),
+            ),
+        ]
+    );
+}
+
+/// Transfer a una dirección muxed acredita la cuenta base
+/// y reporta el id muxed en el evento
+#[test]
+fn test_transfer_to_muxed_address() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob_muxed = MuxedAddress::new(MuxedAddress::generate(&env), 42);
+    let bob = bob_muxed.address();
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.transfer(&alice, &bob_muxed, &100);
+    
+    let mut data: Map<Symbol, Val> = Map::new(&env);
+    data.set(Symbol::new(&env, "amount"), 100i128.into_val(&env));
+    data.set(Symbol::new(&env, "to_muxed_id"), Some(42u64).into_val(&env));
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env),
+                data.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.balance(&bob), 100);
+}
+
+/// approve con expiration_ledger en el pasado debe fallar
+/// (excepto al revocar con amount = 0)
+#[test]
+fn test_approve_past_expiration_fails() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+    
+    let result = client.try_approve(&alice, &bob, &500, &99);
+    assert_eq!(result, Err(Ok(TokenError::InvalidExpiration.into())));
+    
+    // Revocar con un ledger pasado sí está permitido
+    client.approve(&alice, &bob, &0, &0);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+}
+
+/// Un allowance expirado vale 0 y no se puede gastar
+#[test]
+fn test_allowance_expires() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &500, &50);
+    
+    // Todavía válido en el ledger de expiración
+    env.ledger().set_sequence_number(50);
+    assert_eq!(client.allowance(&alice, &bob), 500);
+    
+    // Expirado en el ledger siguiente
+    env.ledger().set_sequence_number(51);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+    
+    let result = client.try_transfer_from(&bob, &alice, &charlie, &100);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
+}
+
+/// Los metadatos no existen antes de initialize() (SEP-41)
+#[test]
+fn test_metadata_before_init_fails() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    assert_eq!(client.try_name(), Err(Ok(TokenError::NotInitialized.into())));
+    assert_eq!(client.try_symbol(), Err(Ok(TokenError::NotInitialized.into())));
+    assert_eq!(client.try_decimals(), Err(Ok(TokenError::NotInitialized.into())));
+}
+
+/// El TTL de la entrada del allowance se ajusta a su expiración
+#[test]
+fn test_allowance_ttl_matches_expiration() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &500, &5_100);
+    
+    let key = DataKey::Allowance(alice.clone(), bob.clone());
+    let ttl = env.as_contract(&contract_id, || {
+        env.storage().persistent().get_ttl(&key)
+    });
+    assert_eq!(ttl, 5_000);
+    
+    // Gastar parte del allowance conserva la expiración original
+    env.ledger().set_sequence_number(1_100);
+    client.transfer_from(&bob, &alice, &charlie, &200);
+    
+    let (ttl, value) = env.as_contract(&contract_id, || {
+        let value: AllowanceValue = env.storage().persistent().get(&key).unwrap();
+        (env.storage().persistent().get_ttl(&key), value)
+    });
+    assert_eq!(ttl, 4_000);
+    assert_eq!(value.amount, 300);
+    assert_eq!(value.expiration_ledger, 5_100);
+}
+
+/// approve con una expiración mayor al TTL máximo de la red debe fallar
+#[test]
+fn test_approve_expiration_beyond_max_ttl_fails() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    let too_far = env.ledger().max_live_until_ledger() + 1;
+    let result = client.try_approve(&alice, &bob, &500, &too_far);
+    assert_eq!(result, Err(Ok(TokenError::InvalidExpiration.into())));
+}
+
+/// Un nuevo approve reemplaza amount y expiración del anterior
+#[test]
+fn test_approve_renews_expired_allowance() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.approve(&alice, &bob, &500, &10);
+    
+    env.ledger().set_sequence_number(20);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+    
+    client.approve(&alice, &bob, &200, &30);
+    assert_eq!(client.allowance(&alice, &bob), 200);
+}
+
+// ============================================================================
+// TESTS DE ROTACIÓN DE ADMIN
+// ============================================================================
+
+/// Transferencia de admin en dos pasos: propose + accept
+#[test]
+fn test_propose_and_accept_admin() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let multisig = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    // Paso 1: el admin propone; el admin todavía no cambia
+    client.propose_admin(&multisig);
+    assert_eq!(client.admin(), admin);
+    assert_eq!(client.pending_admin(), Some(multisig.clone()));
+    
+    // Paso 2: el nuevo admin acepta con su propia firma
+    client.accept_admin();
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            multisig.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "accept_admin"),
+                    ().into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(client.admin(), multisig);
+    assert_eq!(client.pending_admin(), None);
+    
+    // El nuevo admin puede mintear y el anterior ya no
+    client.mint(&multisig, &user, &100);
+    assert_eq!(client.balance(&user), 100);
+    assert_eq!(client.try_mint(&admin, &user, &100), Err(Ok(TokenError::Unauthorized)));
+}
+
+/// accept_admin sin propuesta previa debe fallar
+#[test]
+fn test_accept_admin_without_proposal_fails() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(TokenError::NoPendingAdmin)));
+}
+
+/// renounce_admin deshabilita el minteo para siempre
+#[test]
+fn test_renounce_admin_disables_minting() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let candidate = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &user, &100);
+    
+    // Una propuesta pendiente también se descarta al renunciar
+    client.propose_admin(&candidate);
+    client.renounce_admin();
+    
+    assert_eq!(client.pending_admin(), None);
+    assert_eq!(client.try_mint(&admin, &user, &100), Err(Ok(TokenError::AdminRenounced)));
+    assert_eq!(client.try_propose_admin(&candidate), Err(Ok(TokenError::AdminRenounced)));
+    assert_eq!(client.try_accept_admin(), Err(Ok(TokenError::NoPendingAdmin)));
+    assert_eq!(client.try_admin(), Err(Ok(TokenError::AdminRenounced.into())));
+    
+    // Los holders siguen operando normalmente
+    assert_eq!(client.balance(&user), 100);
+    assert_eq!(client.total_supply(), 100);
+}
+
+// ============================================================================
+// TESTS DE ROLES
+// ============================================================================
+
+/// Un bot con el rol Minter puede mintear sin ser admin
+#[test]
+fn test_minter_role_can_mint() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let bot = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    // Sin rol no puede mintear
+    assert_eq!(client.try_mint(&bot, &user, &100), Err(Ok(TokenError::Unauthorized)));
+    
+    client.grant_role(&admin, &bot, &Role::Minter);
+    client.set_minter_quota(&bot, &1_000, &0, &0);
+    assert!(client.has_role(&bot, &Role::Minter));
+    
+    client.mint(&bot, &user, &100);
+    assert_eq!(client.balance(&user), 100);
+    
+    // El rol Minter no permite administrar roles ni el admin
+    assert_eq!(
+        client.try_grant_role(&bot, &user, &Role::Minter),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    assert!(!client.has_role(&bot, &Role::RoleAdmin));
+}
+
+/// revoke_role y renounce_role quitan el permiso
+#[test]
+fn test_revoke_and_renounce_role() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let bot = Address::generate(&env);
+    let manager = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    // Un RoleAdmin delegado puede otorgar y revocar roles
+    client.grant_role(&admin, &manager, &Role::RoleAdmin);
+    client.grant_role(&manager, &bot, &Role::Minter);
+    client.set_minter_quota(&bot, &1_000, &0, &0);
+    client.mint(&bot, &user, &100);
+    
+    client.revoke_role(&manager, &bot, &Role::Minter);
+    assert!(!client.has_role(&bot, &Role::Minter));
+    assert_eq!(client.try_mint(&bot, &user, &100), Err(Ok(TokenError::Unauthorized)));
+    
+    // El RoleAdmin renuncia a su propio rol
+    client.renounce_role(&manager, &Role::RoleAdmin);
+    assert!(!client.has_role(&manager, &Role::RoleAdmin));
+    assert_eq!(
+        client.try_grant_role(&manager, &bot, &Role::Minter),
+        Err(Ok(TokenError::Unauthorized))
+    );
+}
+
+/// grant_role y revoke_role emiten eventos con role y account como topics
+#[test]
+fn test_role_events() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let bot = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    client.grant_role(&admin, &bot, &Role::Minter);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "role_granted_event"), Role::Minter, bot.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(&env, [(Symbol::new(&env, "caller"), admin.clone().into_val(&env))]).into_val(&env),
+            ),
+        ]
+    );
+    
+    client.revoke_role(&admin, &bot, &Role::Minter);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "role_revoked_event"), Role::Minter, bot.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(&env, [(Symbol::new(&env, "caller"), admin.clone().into_val(&env))]).into_val(&env),
+            ),
+        ]
+    );
+}
+
+// ============================================================================
+// TESTS DE CUPOS DE MINTEO
+// ============================================================================
+
+/// Un minter sin cupo configurado no puede mintear
+#[test]
+fn test_minter_without_quota_fails() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let bot = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.grant_role(&admin, &bot, &Role::Minter);
+    
+    assert_eq!(client.minter_quota(&bot), None);
+    assert_eq!(client.try_mint(&bot, &user, &1), Err(Ok(TokenError::MintQuotaExceeded)));
+}
+
+/// mint descuenta del cupo total y avisa cuando se agota
+#[test]
+fn test_mint_quota_total_cap() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let bot = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.grant_role(&admin, &bot, &Role::Minter);
+    client.set_minter_quota(&bot, &500, &0, &0);
+    
+    client.mint(&bot, &user, &300);
+    assert_eq!(client.minter_quota(&bot).unwrap().remaining, 200);
+    
+    // Superar el cupo restante falla sin mintear nada
+    assert_eq!(client.try_mint(&bot, &user, &201), Err(Ok(TokenError::MintQuotaExceeded)));
+    assert_eq!(client.balance(&user), 300);
+    
+    // Agotar el cupo emite MinterQuotaExhaustedEvent
+    client.mint(&bot, &user, &200);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "minter_quota_exhausted_event"), bot.clone()).into_val(&env),
+                Map::<Symbol, Val>::new(&env).into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), user.clone()).into_val(&env),
+                200i128.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.try_mint(&bot, &user, &1), Err(Ok(TokenError::MintQuotaExceeded)));
+    
+    // El admin no tiene cupo
+    client.mint(&admin, &user, &10_000);
+    assert_eq!(client.balance(&user), 10_500);
+}
+
+/// El límite por ventana se reinicia al pasar window_ledgers
+#[test]
+fn test_mint_quota_window_limit() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let bot = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.grant_role(&admin, &bot, &Role::Minter);
+    
+    // 10_000 en total, máximo 100 cada 50 ledgers
+    client.set_minter_quota(&bot, &10_000, &50, &100);
+    
+    client.mint(&bot, &user, &60);
+    
+    // Agotar el límite de la ventana emite MinterWindowExhaustedEvent
+    client.mint(&bot, &user, &40);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "minter_window_exhausted_event"), bot.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [(Symbol::new(&env, "window_end"), 50u32.into_val(&env))]
+                ).into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), user.clone()).into_val(&env),
+                40i128.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.try_mint(&bot, &user, &1), Err(Ok(TokenError::MintQuotaExceeded)));
+    
+    // Todavía dentro de la misma ventana
+    env.ledger().set_sequence_number(49);
+    assert_eq!(client.try_mint(&bot, &user, &1), Err(Ok(TokenError::MintQuotaExceeded)));
+    
+    // Nueva ventana
+    env.ledger().set_sequence_number(50);
+    client.mint(&bot, &user, &100);
+    
+    let quota = client.minter_quota(&bot).unwrap();
+    assert_eq!(quota.remaining, 9_800);
+    assert_eq!(quota.window_start, 50);
+    assert_eq!(quota.window_minted, 100);
+    assert_eq!(client.balance(&user), 200);
+}
+
+/// Solo el admin puede configurar cupos
+#[test]
+fn test_set_minter_quota_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let bot = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    client.mock_auths(&[MockAuth {
+        address: &bot,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "set_minter_quota",
+            args: (bot.clone(), 1_000i128, 0u32, 0i128).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_set_minter_quota(&bot, &1_000, &0, &0).is_err());
+    
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_set_minter_quota(&bot, &-1, &0, &0),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+}
+
+// ============================================================================
+// TESTS DE TOPE DE EMISIÓN
+// ============================================================================
+
+/// mint no puede superar el max_supply fijado en initialize
+#[test]
+fn test_mint_respects_max_supply() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig { max_supply: Some(1_000), ..Default::default() }
+    );
+    
+    env.mock_all_auths();
+    assert_eq!(client.max_supply(), Some(1_000));
+    
+    client.mint(&admin, &user, &900);
+    assert_eq!(client.try_mint(&admin, &user, &101), Err(Ok(TokenError::SupplyCapExceeded)));
+    
+    // Llegar exactamente al tope está permitido
+    client.mint(&admin, &user, &100);
+    assert_eq!(client.total_supply(), 1_000);
+    
+    // Quemar libera espacio bajo el tope
+    client.burn(&user, &50);
+    client.mint(&admin, &user, &50);
+    assert_eq!(client.total_supply(), 1_000);
+}
+
+/// Un tope inválido en initialize debe fallar
+#[test]
+fn test_initialize_invalid_max_supply() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    
+    let result = client.try_initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig { max_supply: Some(0), ..Default::default() }
+    );
+    assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
+}
+
+/// El tope se puede fijar después de initialize y solo bajar
+#[test]
+fn test_set_max_supply_only_lowers() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    assert_eq!(client.max_supply(), None);
+    client.mint(&admin, &user, &500);
+    
+    // No puede quedar por debajo del supply actual
+    assert_eq!(client.try_set_max_supply(&499), Err(Ok(TokenError::InvalidAmount)));
+    
+    client.set_max_supply(&2_000);
+    assert_eq!(client.max_supply(), Some(2_000));
+    
+    // Subir el tope no está permitido; bajarlo sí
+    assert_eq!(client.try_set_max_supply(&2_001), Err(Ok(TokenError::SupplyCapExceeded)));
+    client.set_max_supply(&600);
+    assert_eq!(client.max_supply(), Some(600));
+    
+    assert_eq!(client.try_mint(&admin, &user, &101), Err(Ok(TokenError::SupplyCapExceeded)));
+}
+
+// ============================================================================
+// TESTS DE PAUSA (CIRCUIT BREAKER)
+// ============================================================================
+
+/// pause(All) detiene todas las operaciones que mueven balances
+#[test]
+fn test_pause_all_blocks_operations() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &500, &EXPIRATION_LEDGER);
+    
+    client.pause(&admin, &PauseScope::All);
+    assert!(client.paused());
+    
+    let paused = Err(Ok(TokenError::Paused.into()));
+    assert_eq!(client.try_mint(&admin, &alice, &1), Err(Ok(TokenError::Paused)));
+    assert_eq!(client.try_transfer(&alice, &bob, &1), paused);
+    assert_eq!(client.try_transfer_from(&bob, &alice, &bob, &1), paused);
+    assert_eq!(client.try_approve(&alice, &bob, &1, &EXPIRATION_LEDGER), paused);
+    assert_eq!(client.try_burn(&alice, &1), paused);
+    assert_eq!(client.try_burn_from(&bob, &alice, &1), paused);
+    
+    // Las consultas siguen funcionando
+    assert_eq!(client.balance(&alice), 1000);
+    
+    client.unpause(&admin, &PauseScope::All);
+    assert!(!client.paused());
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(client.balance(&bob), 100);
+}
+
+/// Pausas granulares: solo minteo o solo transferencias
+#[test]
+fn test_pause_granular_scopes() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    // Solo minteo pausado: las transferencias siguen
+    client.pause(&admin, &PauseScope::Minting);
+    assert_eq!(client.pause_state(), PauseState { transfers: false, minting: true });
+    assert_eq!(client.try_mint(&admin, &alice, &1), Err(Ok(TokenError::Paused)));
+    client.transfer(&alice, &bob, &100);
+    
+    // Solo transferencias pausadas: el minteo sigue
+    client.unpause(&admin, &PauseScope::Minting);
+    client.pause(&admin, &PauseScope::Transfers);
+    assert_eq!(client.pause_state(), PauseState { transfers: true, minting: false });
+    assert_eq!(client.try_transfer(&alice, &bob, &1), Err(Ok(TokenError::Paused.into())));
+    client.mint(&admin, &bob, &50);
+    
+    assert_eq!(client.balance(&alice), 900);
+    assert_eq!(client.balance(&bob), 150);
+}
+
+/// Solo Pauser (o el admin) puede pausar; se emiten eventos
+#[test]
+fn test_pauser_role_and_events() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_pause(&guardian, &PauseScope::All),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    client.grant_role(&admin, &guardian, &Role::Pauser);
+    client.pause(&guardian, &PauseScope::All);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "paused_event"), guardian.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(&env, [(Symbol::new(&env, "scope"), PauseScope::All.into_val(&env))]).into_val(&env),
+            ),
+        ]
+    );
+    
+    client.unpause(&guardian, &PauseScope::All);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "unpaused_event"), guardian.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(&env, [(Symbol::new(&env, "scope"), PauseScope::All.into_val(&env))]).into_val(&env),
+            ),
+        ]
+    );
+    assert!(!client.paused());
+}
+
+// ============================================================================
+// TESTS DE CONGELAMIENTO DE CUENTAS
+// ============================================================================
+
+/// Una cuenta congelada no puede enviar, recibir, quemar ni ser gastada
+#[test]
+fn test_frozen_account_blocked() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.mint(&admin, &bob, &1000);
+    client.approve(&alice, &charlie, &500, &EXPIRATION_LEDGER);
+    
+    client.set_authorized(&alice, &false);
+    assert!(!client.authorized(&alice));
+    
+    let frozen = Err(Ok(TokenError::AccountFrozen.into()));
+    assert_eq!(client.try_transfer(&alice, &bob, &1), frozen);
+    assert_eq!(client.try_transfer(&bob, &alice, &1), frozen);
+    assert_eq!(client.try_burn(&alice, &1), frozen);
+    assert_eq!(client.try_transfer_from(&charlie, &alice, &bob, &1), frozen);
+    assert_eq!(client.try_burn_from(&charlie, &alice, &1), frozen);
+    assert_eq!(client.try_mint(&admin, &alice, &1), Err(Ok(TokenError::AccountFrozen)));
+    
+    // Descongelar restaura la operación normal
+    client.set_authorized(&alice, &true);
+    assert!(client.authorized(&alice));
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(client.balance(&bob), 1100);
+}
+
+/// set_authorized emite el evento con el formato del SAC
+#[test]
+fn test_set_authorized_event() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    // Cuentas nuevas están autorizadas por defecto
+    assert!(client.authorized(&alice));
+    
+    client.set_authorized(&alice, &false);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "set_authorized"), alice.clone()).into_val(&env),
+                false.into_val(&env),
+            ),
+        ]
+    );
+}
+
+// ============================================================================
+// TESTS DE CLAWBACK
+// ============================================================================
+
+/// clawback reduce balance y supply sin firma del holder
+#[test]
+fn test_clawback() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig { clawback_enabled: true, ..Default::default() }
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    // Funciona también sobre cuentas congeladas
+    client.set_authorized(&alice, &false);
+    client.clawback(&admin, &alice, &400);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "clawback"),
+                    (admin.clone(), alice.clone(), 400i128).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "clawback"), alice.clone()).into_val(&env),
+                400i128.into_val(&env),
+            ),
+        ]
+    );
+    
+    assert_eq!(client.balance(&alice), 600);
+    assert_eq!(client.total_supply(), 600);
+    assert_eq!(
+        client.try_clawback(&admin, &alice, &601),
+        Err(Ok(TokenError::InsufficientBalance))
+    );
+}
+
+/// Sin clawback_enabled en initialize, clawback no está disponible nunca
+#[test]
+fn test_clawback_disabled() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    assert!(!client.clawback_enabled());
+    assert_eq!(
+        client.try_clawback(&admin, &alice, &100),
+        Err(Ok(TokenError::ClawbackDisabled))
+    );
+    assert_eq!(client.balance(&alice), 1000);
+}
+
+/// Solo Burner (o el admin) puede hacer clawback
+#[test]
+fn test_clawback_requires_burner_role() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let officer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig { clawback_enabled: true, ..Default::default() }
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    assert_eq!(
+        client.try_clawback(&officer, &alice, &100),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    client.grant_role(&admin, &officer, &Role::Burner);
+    client.clawback(&officer, &alice, &100);
+    assert_eq!(client.balance(&alice), 900);
+}
+
+// ============================================================================
+// TESTS DE BURN_FROM
+// ============================================================================
+
+/// burn_from quema usando el allowance, como transfer_from
+#[test]
+fn test_burn_from() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let redeemer = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &redeemer, &300, &EXPIRATION_LEDGER);
+    
+    client.burn_from(&redeemer, &alice, &200);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("burn"), alice.clone()).into_val(&env),
+                200i128.into_val(&env),
+            ),
+        ]
+    );
+    
+    assert_eq!(client.balance(&alice), 800);
+    assert_eq!(client.total_supply(), 800);
+    assert_eq!(client.allowance(&alice, &redeemer), 100);
+}
+
+/// burn_from con allowance insuficiente debe fallar
+#[test]
+fn test_burn_from_insufficient_allowance() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let redeemer = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &redeemer, &100, &EXPIRATION_LEDGER);
+    
+    let result = client.try_burn_from(&redeemer, &alice, &101);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
+    assert_eq!(client.balance(&alice), 1000);
+}
+
+/// Consumir todo el allowance con burn_from elimina la key
+#[test]
+fn test_burn_from_exhausts_allowance() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let redeemer = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &redeemer, &250, &EXPIRATION_LEDGER);
+    client.burn_from(&redeemer, &alice, &250);
+    
+    assert_eq!(client.allowance(&alice, &redeemer), 0);
+    let has_key = env.as_contract(&contract_id, || {
+        env.storage().persistent().has(&DataKey::Allowance(alice.clone(), redeemer.clone()))
+    });
+    assert!(!has_key);
+    
+    let result = client.try_burn_from(&redeemer, &alice, &1);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
+}
+
+// ============================================================================
+// TESTS DE PERMIT (APPROVALS FIRMADOS OFF-CHAIN)
+// ============================================================================
+
+/// Dirección G... de una clave ed25519, construida vía XDR
+fn account_from_key(env: &Env, key: &SigningKey) -> Address {
+    let sc_address = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(
+        Uint256(key.verifying_key().to_bytes()),
+    )));
+    Address::try_from_val(env, &sc_address).unwrap()
+}
+
+/// Firma el digest del permit como lo haría la wallet del owner
+fn sign_permit(
+    env: &Env,
+    client: &TokenBDBClient,
+    key: &SigningKey,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u64,
+) -> (BytesN<32>, BytesN<64>) {
+    let pubkey = BytesN::from_array(env, &key.verifying_key().to_bytes());
+    let digest = client.permit_digest(&pubkey, spender, &amount, &expiration_ledger, &nonce);
+    let signature = key.sign(&digest.to_array()).to_bytes();
+    (pubkey, BytesN::from_array(env, &signature))
+}
+
+/// Un relayer envía el permit firmado y el spender puede gastar
+#[test]
+fn test_permit_sets_allowance() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let owner_key = SigningKey::from_bytes(&[7u8; 32]);
+    let owner = account_from_key(&env, &owner_key);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &owner, &1000);
+    
+    let (pubkey, signature) = sign_permit(&env, &client, &owner_key, &spender, 300, EXPIRATION_LEDGER, 0);
+    
+    // permit no requiere ninguna autorización on-chain del owner
+    env.set_auths(&[]);
+    client.permit(&pubkey, &spender, &300, &EXPIRATION_LEDGER, &0, &signature);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("approve"), owner.clone(), spender.clone()).into_val(&env),
+                (300i128, EXPIRATION_LEDGER).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.allowance(&owner, &spender), 300);
+    assert_eq!(client.permit_nonce(&owner), 1);
+    
+    env.mock_all_auths();
+    client.transfer_from(&spender, &owner, &charlie, &300);
+    assert_eq!(client.balance(&charlie), 300);
+}
+
+/// Una firma no se puede reutilizar (nonce consumido)
+#[test]
+fn test_permit_replay_fails() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let owner_key = SigningKey::from_bytes(&[7u8; 32]);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    let (pubkey, signature) = sign_permit(&env, &client, &owner_key, &spender, 300, EXPIRATION_LEDGER, 0);
+    client.permit(&pubkey, &spender, &300, &EXPIRATION_LEDGER, &0, &signature);
+    
+    let result = client.try_permit(&pubkey, &spender, &300, &EXPIRATION_LEDGER, &0, &signature);
+    assert_eq!(result, Err(Ok(TokenError::InvalidNonce)));
+}
+
+/// Una firma sobre otros parámetros (o de otra clave) es rechazada
+#[test]
+fn test_permit_invalid_signature_fails() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let owner_key = SigningKey::from_bytes(&[7u8; 32]);
+    let attacker_key = SigningKey::from_bytes(&[9u8; 32]);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    // El owner firmó 300, el relayer intenta enviar 3000
+    let (pubkey, signature) = sign_permit(&env, &client, &owner_key, &spender, 300, EXPIRATION_LEDGER, 0);
+    assert!(client.try_permit(&pubkey, &spender, &3000, &EXPIRATION_LEDGER, &0, &signature).is_err());
+    
+    // Firma de otra clave para la clave pública del owner
+    let (_, forged) = sign_permit(&env, &client, &attacker_key, &spender, 300, EXPIRATION_LEDGER, 0);
+    assert!(client.try_permit(&pubkey, &spender, &300, &EXPIRATION_LEDGER, &0, &forged).is_err());
+    
+    let owner = account_from_key(&env, &owner_key);
+    assert_eq!(client.allowance(&owner, &spender), 0);
+    assert_eq!(client.permit_nonce(&owner), 0);
+}
+
+// ============================================================================
+// TESTS DE BATCH TRANSFER
+// ============================================================================
+
+/// Un lote paga a varios destinatarios con un evento por partida
+#[test]
+fn test_batch_transfer() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    let recipients = vec![
+        &env,
+        (bob.clone(), 100i128),
+        (charlie.clone(), 200i128),
+        (bob.clone(), 50i128),
+    ];
+    client.batch_transfer(&alice, &recipients);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env),
+                100i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), charlie.clone()).into_val(&env),
+                200i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env),
+                50i128.into_val(&env),
+            ),
+        ]
+    );
+    
+    // Una sola autorización de alice para todo el lote
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            alice.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "batch_transfer"),
+                    (alice.clone(), recipients.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    
+    assert_eq!(client.balance(&alice), 650);
+    assert_eq!(client.balance(&bob), 150);
+    assert_eq!(client.balance(&charlie), 200);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Si una partida es inválida no se aplica ninguna
+#[test]
+fn test_batch_transfer_is_atomic() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    // Amount inválido en la última partida
+    let result = client.try_batch_transfer(
+        &alice,
+        &vec![&env, (bob.clone(), 100i128), (charlie.clone(), 0i128)]
+    );
+    assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
+    
+    // Destinatario congelado
+    client.set_authorized(&charlie, &false);
+    let result = client.try_batch_transfer(
+        &alice,
+        &vec![&env, (bob.clone(), 100i128), (charlie.clone(), 100i128)]
+    );
+    assert_eq!(result, Err(Ok(TokenError::AccountFrozen)));
+    
+    // El total del lote supera el balance
+    let result = client.try_batch_transfer(
+        &alice,
+        &vec![&env, (bob.clone(), 600i128), (bob.clone(), 600i128)]
+    );
+    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance)));
+    
+    // Lote vacío
+    let result = client.try_batch_transfer(&alice, &vec![&env]);
+    assert_eq!(result, Err(Ok(TokenError::EmptyBatch)));
+    
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.balance(&bob), 0);
+    assert_eq!(client.balance(&charlie), 0);
+}
+
+/// batch_transfer respeta el circuit breaker de transferencias
+#[test]
+fn test_batch_transfer_paused() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.pause(&admin, &PauseScope::Transfers);
+    
+    let result = client.try_batch_transfer(&alice, &vec![&env, (bob.clone(), 100i128)]);
+    assert_eq!(result, Err(Ok(TokenError::Paused)));
+    assert_eq!(client.balance(&alice), 1000);
+}
+
+// ============================================================================
+// TESTS DE MINT EN LOTE
+// ============================================================================
+
+/// mint_batch acredita cada partida y actualiza el supply una vez
+#[test]
+fn test_mint_batch() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint_batch(&admin, &vec![&env, (alice.clone(), 100i128), (bob.clone(), 250i128)]);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), alice.clone()).into_val(&env),
+                100i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), bob.clone()).into_val(&env),
+                250i128.into_val(&env),
+            ),
+        ]
+    );
+    
+    assert_eq!(client.balance(&alice), 100);
+    assert_eq!(client.balance(&bob), 250);
+    assert_eq!(client.total_supply(), 350);
+}
+
+/// Cupo y tope se aplican al total del lote, sin mintear nada si fallan
+#[test]
+fn test_mint_batch_respects_quota_and_cap() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig { max_supply: Some(1000), ..Default::default() }
+    );
+    
+    env.mock_all_auths();
+    client.grant_role(&admin, &minter, &Role::Minter);
+    client.set_minter_quota(&minter, &500, &0, &0);
+    
+    // Cada partida entra en el cupo pero el total no
+    let result = client.try_mint_batch(&minter, &vec![&env, (alice.clone(), 300i128), (bob.clone(), 300i128)]);
+    assert_eq!(result, Err(Ok(TokenError::MintQuotaExceeded)));
+    
+    // El admin no tiene cupo, pero el lote supera el tope de emisión
+    let result = client.try_mint_batch(&admin, &vec![&env, (alice.clone(), 600i128), (bob.clone(), 600i128)]);
+    assert_eq!(result, Err(Ok(TokenError::SupplyCapExceeded)));
+    
+    assert_eq!(client.balance(&alice), 0);
+    assert_eq!(client.balance(&bob), 0);
+    assert_eq!(client.total_supply(), 0);
+    assert_eq!(client.minter_quota(&minter).unwrap().remaining, 500);
+}
+
+/// El tamaño máximo de lote es configurable por el admin
+#[test]
+fn test_batch_size_limit() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    assert_eq!(client.max_batch_size(), 7);
+    client.set_max_batch_size(&2);
+    assert_eq!(client.max_batch_size(), 2);
+    
+    let recipients = vec![
+        &env,
+        (alice.clone(), 100i128),
+        (bob.clone(), 100i128),
+        (charlie.clone(), 100i128),
+    ];
+    assert_eq!(client.try_mint_batch(&admin, &recipients), Err(Ok(TokenError::BatchTooLarge)));
+    
+    client.mint(&admin, &alice, &1000);
+    assert_eq!(client.try_batch_transfer(&alice, &recipients), Err(Ok(TokenError::BatchTooLarge)));
+    
+    assert_eq!(client.try_set_max_batch_size(&0), Err(Ok(TokenError::InvalidAmount)));
+    
+    // Un lote completo tiene que caber en el límite de escrituras de la red
+    assert_eq!(client.try_set_max_batch_size(&8), Err(Ok(TokenError::BatchTooLarge)));
+    client.set_max_batch_size(&7);
+    assert_eq!(client.max_batch_size(), 7);
+}
+
+/// Mide el presupuesto consumido por un lote del tamaño máximo por defecto
+///
+/// Un lote de DEFAULT_MAX_BATCH_SIZE debe caber en el presupuesto por
+/// defecto del host y en el límite de entradas escritas por
+/// transacción, con un costo acotado por destinatario, y costar
+/// menos que los mints individuales
+#[test]
+fn test_mint_batch_budget() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    // Peor caso de escrituras: cada destinatario ya tiene historial y
+    // delega en una cuenta propia, desde un ledger anterior al del lote
+    let n = client.max_batch_size();
+    let mut recipients = Vec::new(&env);
+    for _ in 0..n {
+        let to = Address::generate(&env);
+        client.mint(&admin, &to, &10);
+        client.delegate(&to, &Address::generate(&env));
+        recipients.push_back((to, 10i128));
+    }
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+    
+    // Lote completo: tiene que caber en el presupuesto por defecto...
+    env.cost_estimate().budget().reset_default();
+    client.mint_batch(&admin, &recipients);
+    let batch_cpu = env.cost_estimate().budget().cpu_instruction_cost();
+    let batch_mem = env.cost_estimate().budget().memory_bytes_cost();
+    
+    // ...y en el límite de entradas escritas por transacción
+    let write_entries = env.cost_estimate().resources().write_entries;
+    // (instance, SupplyHistory, SupplyCheckpoint y el nonce del admin)
+    assert_eq!(write_entries, 4 + BATCH_WRITE_ENTRIES_PER_LEG * n);
+    assert!(write_entries <= MAX_WRITE_ENTRIES);
+    
+    // Costo por destinatario (hoy ~450k instrucciones y ~86 KB);
+    // un aumento grande indica más keys de storage por partida
+    assert!(batch_cpu / (n as u64) < 600_000);
+    assert!(batch_mem / (n as u64) < 120_000);
+    
+    // Los mismos N mints por separado
+    let mut single_cpu = 0;
+    for (to, amount) in recipients.iter() {
+        env.cost_estimate().budget().reset_default();
+        client.mint(&admin, &to, &amount);
+        single_cpu += env.cost_estimate().budget().cpu_instruction_cost();
+    }
+    
+    assert!(batch_cpu < single_cpu);
+    assert_eq!(client.total_supply(), 3 * 10 * n as i128);
+}
+
+/// Un batch_transfer del tamaño máximo cabe en el límite de escrituras
+///
+/// Peor caso: sender, tesorería y destinatarios con historial previo y
+/// un delegado propio cada uno, y una comisión en cada partida
+#[test]
+fn test_batch_transfer_write_entries() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.set_fee_policy(&Some(fee_policy(&treasury, Vec::new(&env))));
+    
+    client.mint(&admin, &alice, &100_000);
+    client.delegate(&alice, &Address::generate(&env));
+    client.mint(&admin, &treasury, &10);
+    client.delegate(&treasury, &Address::generate(&env));
+    
+    let n = client.max_batch_size();
+    let mut recipients = Vec::new(&env);
+    for _ in 0..n {
+        let to = Address::generate(&env);
+        client.mint(&admin, &to, &10);
+        client.delegate(&to, &Address::generate(&env));
+        recipients.push_back((to, 1000i128));
+    }
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+    
+    client.batch_transfer(&alice, &recipients);
+    let write_entries = env.cost_estimate().resources().write_entries;
+    assert_eq!(write_entries, BATCH_BASE_WRITE_ENTRIES + BATCH_WRITE_ENTRIES_PER_LEG * n);
+    assert!(write_entries <= MAX_WRITE_ENTRIES);
+    
+    assert_eq!(client.balance(&alice), 100_000 - 1000 * n as i128);
+    assert_eq!(client.balance(&treasury), 10 + 10 * n as i128);
+}
+
+// ============================================================================
+// TESTS DE AIRDROP (MERKLE)
+// ============================================================================
+
+/// Nodo interno del árbol: sha256 del par ordenado
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (a, b) = if a.to_array() <= b.to_array() { (a, b) } else { (b, a) };
+    let mut pair = Bytes::from_array(env, &a.to_array());
+    pair.extend_from_array(&b.to_array());
+    env.crypto().sha256(&pair).into()
+}
+
+/// Árbol de 4 hojas; devuelve (raíz, hojas)
+fn airdrop_tree(
+    env: &Env,
+    client: &TokenBDBClient,
+    claims: &[(Address, i128); 4],
+) -> (BytesN<32>, std::vec::Vec<BytesN<32>>) {
+    let leaves: std::vec::Vec<BytesN<32>> = claims
+        .iter()
+        .enumerate()
+        .map(|(i, (account, amount))| client.airdrop_leaf(&(i as u32), account, amount))
+        .collect();
+    let left = hash_pair(env, &leaves[0], &leaves[1]);
+    let right = hash_pair(env, &leaves[2], &leaves[3]);
+    (hash_pair(env, &left, &right), leaves)
+}
+
+/// Los holders reclaman su parte con una prueba de Merkle
+#[test]
+fn test_airdrop_claim() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let claims = [
+        (Address::generate(&env), 100i128),
+        (Address::generate(&env), 200i128),
+        (Address::generate(&env), 300i128),
+        (Address::generate(&env), 400i128),
+    ];
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    let (root, leaves) = airdrop_tree(&env, &client, &claims);
+    client.start_airdrop(&root, &1000, &EXPIRATION_LEDGER);
+    assert_eq!(client.balance(&contract_id), 1000);
+    assert_eq!(client.total_supply(), 1000);
+    
+    // claim no requiere autorización de nadie
+    env.set_auths(&[]);
+    let (alice, alice_amount) = &claims[0];
+    let proof = vec![&env, leaves[1].clone(), hash_pair(&env, &leaves[2], &leaves[3])];
+    client.claim(&0, alice, alice_amount, &proof);
+    
+    let (charlie, charlie_amount) = &claims[2];
+    let proof = vec![&env, leaves[3].clone(), hash_pair(&env, &leaves[0], &leaves[1])];
+    client.claim(&2, charlie, charlie_amount, &proof);
+    
+    assert_eq!(client.balance(alice), 100);
+    assert_eq!(client.balance(charlie), 300);
+    assert_eq!(client.balance(&contract_id), 600);
+    assert_eq!(client.airdrop().unwrap().remaining, 600);
+    assert!(client.is_claimed(&0));
+    assert!(!client.is_claimed(&1));
+    assert!(client.is_claimed(&2));
+}
+
+/// Claims repetidos, con datos falsos o fuera de plazo son rechazados
+#[test]
+fn test_airdrop_claim_rejects_invalid() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let claims = [
+        (Address::generate(&env), 100i128),
+        (Address::generate(&env), 200i128),
+        (Address::generate(&env), 300i128),
+        (Address::generate(&env), 400i128),
+    ];
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    let (root, leaves) = airdrop_tree(&env, &client, &claims);
+    client.start_airdrop(&root, &1000, &EXPIRATION_LEDGER);
+    
+    let (alice, _) = &claims[0];
+    let (bob, bob_amount) = &claims[1];
+    let proof = vec![&env, leaves[0].clone(), hash_pair(&env, &leaves[2], &leaves[3])];
+    
+    // Monto inflado o prueba usada para otra cuenta
+    assert_eq!(client.try_claim(&1, bob, &1000, &proof), Err(Ok(TokenError::InvalidProof)));
+    assert_eq!(client.try_claim(&1, alice, bob_amount, &proof), Err(Ok(TokenError::InvalidProof)));
+    
+    client.claim(&1, bob, bob_amount, &proof);
+    assert_eq!(client.try_claim(&1, bob, bob_amount, &proof), Err(Ok(TokenError::AlreadyClaimed)));
+    
+    // Fuera de plazo
+    env.ledger().set_sequence_number(EXPIRATION_LEDGER + 1);
+    let (charlie, charlie_amount) = &claims[2];
+    let proof = vec![&env, leaves[3].clone(), hash_pair(&env, &leaves[0], &leaves[1])];
+    assert_eq!(client.try_claim(&2, charlie, charlie_amount, &proof), Err(Ok(TokenError::AirdropExpired)));
+    
+    assert_eq!(client.balance(bob), 200);
+    assert_eq!(client.balance(charlie), 0);
+}
+
+/// Después del deadline el admin recupera la reserva no reclamada
+#[test]
+fn test_airdrop_sweep() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let claims = [
+        (Address::generate(&env), 100i128),
+        (Address::generate(&env), 200i128),
+        (Address::generate(&env), 300i128),
+        (Address::generate(&env), 400i128),
+    ];
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    let (root, leaves) = airdrop_tree(&env, &client, &claims);
+    client.start_airdrop(&root, &1000, &EXPIRATION_LEDGER);
+    assert_eq!(client.try_start_airdrop(&root, &1000, &EXPIRATION_LEDGER), Err(Ok(TokenError::AirdropActive)));
+    
+    let (dave, dave_amount) = &claims[3];
+    let proof = vec![&env, leaves[2].clone(), hash_pair(&env, &leaves[0], &leaves[1])];
+    client.claim(&3, dave, dave_amount, &proof);
+    
+    assert_eq!(client.try_sweep_airdrop(&admin), Err(Ok(TokenError::AirdropNotEnded)));
+    
+    env.ledger().set_sequence_number(EXPIRATION_LEDGER + 1);
+    client.sweep_airdrop(&admin);
+    assert_eq!(client.balance(&admin), 600);
+    assert_eq!(client.balance(&contract_id), 0);
+    assert_eq!(client.airdrop(), None);
+    assert_eq!(client.total_supply(), 1000);
+    
+    // Una ronda nueva no hereda el bitmap de la anterior
+    client.start_airdrop(&root, &1000, &(EXPIRATION_LEDGER + 1_000));
+    assert_eq!(client.airdrop().unwrap().id, 1);
+    assert!(!client.is_claimed(&3));
+    client.claim(&3, dave, dave_amount, &proof);
+    assert_eq!(client.balance(dave), 800);
+}
+
+// ============================================================================
+// TESTS DE VESTING
+// ============================================================================
+
+/// Nada antes del cliff, lineal después y todo al final
+#[test]
+fn test_vesting_release() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+    let id = client.create_vesting(&alice, &1000, &100, &300, &1100, &false);
+    assert_eq!(client.balance(&contract_id), 1000);
+    assert_eq!(client.total_supply(), 1000);
+    
+    // Antes del cliff
+    env.ledger().set_sequence_number(299);
+    assert_eq!(client.releasable(&id), 0);
+    assert_eq!(client.try_release(&id), Err(Ok(TokenError::NothingToRelease)));
+    
+    // En el cliff se libera todo lo acumulado desde start
+    env.ledger().set_sequence_number(300);
+    assert_eq!(client.release(&id), 200);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            alice.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "release"),
+                    (id,).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    
+    env.ledger().set_sequence_number(600);
+    assert_eq!(client.release(&id), 300);
+    
+    // Después de end_ledger se libera el resto
+    env.ledger().set_sequence_number(5_000);
+    assert_eq!(client.release(&id), 500);
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.balance(&contract_id), 0);
+    assert_eq!(client.vesting(&id).unwrap().released, 1000);
+}
+
+/// Revocar devuelve lo no liberado al admin y respeta lo ya liberado
+#[test]
+fn test_vesting_revoke() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    let revocable = client.create_vesting(&alice, &1000, &0, &0, &1000, &true);
+    let fixed = client.create_vesting(&bob, &1000, &0, &0, &1000, &false);
+    
+    env.ledger().set_sequence_number(250);
+    client.revoke_vesting(&revocable, &admin);
+    assert_eq!(client.balance(&admin), 750);
+    assert_eq!(client.try_revoke_vesting(&revocable, &admin), Err(Ok(TokenError::NotRevocable)));
+    assert_eq!(client.try_revoke_vesting(&fixed, &admin), Err(Ok(TokenError::NotRevocable)));
+    
+    // Lo liberado antes de revocar sigue siendo de alice, y nada más
+    env.ledger().set_sequence_number(900);
+    assert_eq!(client.release(&revocable), 250);
+    assert_eq!(client.try_release(&revocable), Err(Ok(TokenError::NothingToRelease)));
+    assert_eq!(client.balance(&alice), 250);
+    
+    // El calendario no revocable sigue su curso
+    assert_eq!(client.release(&fixed), 900);
+    assert_eq!(client.balance(&contract_id), 100);
+}
+
+/// La reserva de vesting y airdrops no se congela, sanciona ni recupera
+#[test]
+fn test_reserve_is_protected() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig { clawback_enabled: true, ..Default::default() }
+    );
+    
+    env.mock_all_auths();
+    let id = client.create_vesting(&alice, &1000, &0, &0, &1000, &false);
+    client.start_airdrop(&BytesN::from_array(&env, &[7u8; 32]), &500, &100);
+    assert_eq!(client.balance(&contract_id), 1500);
+    
+    assert_eq!(
+        client.try_clawback(&admin, &contract_id, &100),
+        Err(Ok(TokenError::ReservedAccount))
+    );
+    assert_eq!(client.try_set_authorized(&contract_id, &false), Err(Ok(TokenError::ReservedAccount)));
+    assert_eq!(
+        client.try_set_denylisted(&admin, &vec![&env, contract_id.clone()], &true),
+        Err(Ok(TokenError::ReservedAccount))
+    );
+    
+    // La reserva sigue intacta para release() y sweep_airdrop()
+    env.ledger().set_sequence_number(1000);
+    assert_eq!(client.release(&id), 1000);
+    client.sweep_airdrop(&admin);
+    assert_eq!(client.balance(&admin), 500);
+    assert_eq!(client.balance(&contract_id), 0);
+}
+
+/// Fechas inválidas o tope de emisión impiden crear el calendario
+#[test]
+fn test_vesting_invalid_schedule() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig { max_supply: Some(500), ..Default::default() }
+    );
+    
+    env.mock_all_auths();
+    assert_eq!(client.try_create_vesting(&alice, &100, &100, &100, &100, &false), Err(Ok(TokenError::InvalidSchedule)));
+    assert_eq!(client.try_create_vesting(&alice, &100, &100, &50, &200, &false), Err(Ok(TokenError::InvalidSchedule)));
+    assert_eq!(client.try_create_vesting(&alice, &100, &100, &300, &200, &false), Err(Ok(TokenError::InvalidSchedule)));
+    assert_eq!(client.try_create_vesting(&alice, &1000, &0, &0, &100, &false), Err(Ok(TokenError::SupplyCapExceeded)));
+    assert_eq!(client.try_release(&0), Err(Ok(TokenError::VestingNotFound)));
+    assert_eq!(client.total_supply(), 0);
+}
+
+// ============================================================================
+// TESTS DE CHECKPOINTS (CONSULTAS HISTÓRICAS)
+// ============================================================================
+
+/// balance_at y total_supply_at devuelven el valor al final de cada ledger
+#[test]
+fn test_balance_and_supply_history() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &alice, &1000);
+    // Dos cambios en el mismo ledger: vale el último
+    client.transfer(&alice, &bob, &100);
+    
+    env.ledger().set_sequence_number(20);
+    client.transfer(&alice, &bob, &400);
+    
+    env.ledger().set_sequence_number(30);
+    client.burn(&bob, &500);
+    
+    env.ledger().set_sequence_number(40);
+    
+    assert_eq!(client.balance_at(&alice, &9), 0);
+    assert_eq!(client.balance_at(&alice, &10), 900);
+    assert_eq!(client.balance_at(&alice, &19), 900);
+    assert_eq!(client.balance_at(&alice, &20), 500);
+    assert_eq!(client.balance_at(&alice, &39), 500);
+    assert_eq!(client.balance_at(&bob, &10), 100);
+    assert_eq!(client.balance_at(&bob, &25), 500);
+    assert_eq!(client.balance_at(&bob, &30), 0);
+    
+    assert_eq!(client.total_supply_at(&9), 0);
+    assert_eq!(client.total_supply_at(&10), 1000);
+    assert_eq!(client.total_supply_at(&29), 1000);
+    assert_eq!(client.total_supply_at(&30), 500);
+    
+    // Una entrada por ledger con cambios
+    env.as_contract(&contract_id, || {
+        let history: History = env.storage().persistent()
+            .get(&DataKey::BalanceHistory(alice.clone()))
+            .unwrap();
+        assert_eq!(history, History { count: 1, ledger: 20, value: 500 });
+    });
+}
+
+/// Balances y supply anteriores al historial (contrato actualizado con
+/// upgrade) se reportan igual antes y después de su primer cambio
+#[test]
+fn test_history_before_upgrade() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &alice, &1000);
+    
+    // Simula un contrato desplegado antes del historial: hay
+    // Balance y TotalSupply, pero ninguna entrada de historial
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().remove(&DataKey::BalanceHistory(alice.clone()));
+        env.storage().persistent().remove(&DataKey::SupplyHistory);
+    });
+    
+    env.ledger().set_sequence_number(20);
+    assert_eq!(client.balance_at(&alice, &15), 1000);
+    assert_eq!(client.total_supply_at(&15), 1000);
+    
+    // El primer cambio conserva el valor anterior
+    client.transfer(&alice, &bob, &400);
+    client.mint(&admin, &bob, &100);
+    
+    env.ledger().set_sequence_number(30);
+    assert_eq!(client.balance_at(&alice, &15), 1000);
+    assert_eq!(client.balance_at(&alice, &20), 600);
+    assert_eq!(client.balance_at(&bob, &15), 0);
+    assert_eq!(client.balance_at(&bob, &20), 500);
+    assert_eq!(client.total_supply_at(&15), 1000);
+    assert_eq!(client.total_supply_at(&20), 1100);
+}
+
+/// Consultar el historial extiende el TTL de las entradas que lee
+#[test]
+fn test_history_lookup_extends_ttl() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &alice, &1000);
+    env.ledger().set_sequence_number(20);
+    client.transfer(&alice, &bob, &400);
+    
+    // La entrada del ledger 10 se escribió una sola vez...
+    env.ledger().set_sequence_number(150_000);
+    let checkpoint = DataKey::BalanceCheckpoint(alice.clone(), 0);
+    env.as_contract(&contract_id, || {
+        assert!(env.storage().persistent().get_ttl(&checkpoint) < 100_000);
+    });
+    
+    // ...y leerla la mantiene viva
+    assert_eq!(client.balance_at(&alice, &15), 1000);
+    env.as_contract(&contract_id, || {
+        assert_eq!(env.storage().persistent().get_ttl(&checkpoint), 200_000);
+        assert_eq!(
+            env.storage().persistent().get_ttl(&DataKey::BalanceHistory(alice.clone())),
+            200_000
+        );
+    });
+}
+
+/// Solo se consultan ledgers pasados: el actual todavía puede cambiar
+#[test]
+fn test_history_rejects_current_ledger() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &alice, &1000);
+    
+    assert_eq!(client.try_balance_at(&alice, &10), Err(Ok(TokenError::FutureLedger)));
+    assert_eq!(client.try_total_supply_at(&11), Err(Ok(TokenError::FutureLedger)));
+}
+
+// ============================================================================
+// TESTS DE VOTOS Y DELEGACIÓN
+// ============================================================================
+
+/// Delegar mueve el balance como poder de voto y emite los eventos
+#[test]
+fn test_delegate_votes() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    // Sin delegar, el balance no cuenta como voto
+    assert_eq!(client.delegates(&alice), None);
+    assert_eq!(client.get_votes(&alice), 0);
+    
+    client.delegate(&alice, &bob);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "delegate_changed_event"), alice.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [
+                        (Symbol::new(&env, "from_delegate"), Option::<Address>::None.into_val(&env)),
+                        (Symbol::new(&env, "to_delegate"), bob.into_val(&env)),
+                    ]
+                ).into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "delegate_votes_changed_event"), bob.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [
+                        (Symbol::new(&env, "new_votes"), 1000i128.into_val(&env)),
+                        (Symbol::new(&env, "previous_votes"), 0i128.into_val(&env)),
+                    ]
+                ).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.delegates(&alice), Some(bob.clone()));
+    assert_eq!(client.get_votes(&bob), 1000);
+    
+    // Los cambios de balance siguen al delegado
+    client.transfer(&alice, &admin, &300);
+    assert_eq!(client.get_votes(&bob), 700);
+    client.mint(&admin, &alice, &50);
+    assert_eq!(client.get_votes(&bob), 750);
+    
+    // Redelegar en sí misma
+    client.delegate(&alice, &alice);
+    assert_eq!(client.get_votes(&bob), 0);
+    assert_eq!(client.get_votes(&alice), 750);
+}
+
+/// get_past_votes usa el historial: tokens recibidos después no cuentan
+#[test]
+fn test_past_votes() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &alice, &1000);
+    client.mint(&admin, &bob, &500);
+    client.delegate(&alice, &alice);
+    client.delegate(&bob, &bob);
+    
+    // Snapshot en el ledger 10; bob recibe tokens después
+    env.ledger().set_sequence_number(20);
+    client.transfer(&alice, &bob, &1000);
+    
+    env.ledger().set_sequence_number(30);
+    assert_eq!(client.get_past_votes(&alice, &10), 1000);
+    assert_eq!(client.get_past_votes(&bob, &10), 500);
+    assert_eq!(client.get_past_votes(&alice, &20), 0);
+    assert_eq!(client.get_past_votes(&bob, &20), 1500);
+    assert_eq!(client.get_past_votes(&bob, &5), 0);
+    assert_eq!(client.try_get_past_votes(&bob, &30), Err(Ok(TokenError::FutureLedger)));
+}
+
+// ============================================================================
+// TESTS DE COMISIÓN POR TRANSFERENCIA
+// ============================================================================
+
+/// Política de 1% con mínimo 2 y máximo 50 hacia `treasury`
+fn fee_policy(treasury: &Address, exempt: Vec<Address>) -> FeePolicy {
+    FeePolicy {
+        bps: 100,
+        min_fee: 2,
+        max_fee: 50,
+        treasury: treasury.clone(),
+        exempt,
+    }
+}
+
+/// transfer descuenta la comisión de lo recibido y la envía a la tesorería
+#[test]
+fn test_transfer_fee() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &100_000);
+    client.set_fee_policy(&Some(fee_policy(&treasury, Vec::new(&env))));
+    
+    client.transfer(&alice, &bob, &1000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env),
+                990i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), treasury.clone()).into_val(&env),
+                10i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "fee_event"), alice.clone(), treasury.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [(Symbol::new(&env, "amount"), 10i128.into_val(&env))]
+                ).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.balance(&alice), 99_000);
+    assert_eq!(client.balance(&bob), 990);
+    assert_eq!(client.balance(&treasury), 10);
+    
+    // Mínimo y máximo; el mínimo nunca supera el 5% del monto
+    assert_eq!(client.transfer_fee(&alice, &bob, &50), 2);
+    assert_eq!(client.transfer_fee(&alice, &bob, &20), 1);
+    assert_eq!(client.transfer_fee(&alice, &bob, &1), 0);
+    assert_eq!(client.transfer_fee(&alice, &bob, &50_000), 50);
+    
+    // transfer_from también paga comisión
+    client.approve(&alice, &admin, &10_000, &EXPIRATION_LEDGER);
+    client.transfer_from(&admin, &alice, &bob, &10_000);
+    assert_eq!(client.balance(&bob), 990 + 9_950);
+    assert_eq!(client.balance(&treasury), 10 + 50);
+    assert_eq!(client.total_supply(), 100_000);
+}
+
+/// Cuentas exentas, tesorería y política eliminada no pagan comisión
+#[test]
+fn test_transfer_fee_exemptions() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let exchange = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &10_000);
+    client.set_fee_policy(&Some(fee_policy(&treasury, vec![&env, exchange.clone()])));
+    
+    client.transfer(&alice, &exchange, &1000);
+    client.transfer(&exchange, &bob, &500);
+    client.transfer(&alice, &treasury, &1000);
+    assert_eq!(client.balance(&exchange), 500);
+    assert_eq!(client.balance(&bob), 500);
+    assert_eq!(client.balance(&treasury), 1000);
+    
+    client.set_fee_policy(&None);
+    assert_eq!(client.fee_policy(), None);
+    client.transfer(&alice, &bob, &1000);
+    assert_eq!(client.balance(&bob), 1500);
+}
+
+/// La política no puede superar el techo fijo
+#[test]
+fn test_fee_policy_ceiling() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    let mut policy = fee_policy(&treasury, Vec::new(&env));
+    policy.bps = 501;
+    assert_eq!(client.try_set_fee_policy(&Some(policy.clone())), Err(Ok(TokenError::InvalidFeePolicy)));
+    
+    policy.bps = 500;
+    policy.min_fee = 100;
+    policy.max_fee = 10;
+    assert_eq!(client.try_set_fee_policy(&Some(policy.clone())), Err(Ok(TokenError::InvalidFeePolicy)));
+    
+    // La lista de exentas está acotada
+    policy.max_fee = 100;
+    let mut exempt = Vec::new(&env);
+    for _ in 0..21 {
+        exempt.push_back(Address::generate(&env));
+    }
+    policy.exempt = exempt;
+    assert_eq!(client.try_set_fee_policy(&Some(policy.clone())), Err(Ok(TokenError::InvalidFeePolicy)));
+    
+    policy.exempt.pop_back();
+    client.set_fee_policy(&Some(policy.clone()));
+    assert_eq!(client.fee_policy(), Some(policy.clone()));
+    
+    // Un min_fee enorme no se salta el techo: como mucho 5% del monto
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    policy.exempt = Vec::new(&env);
+    policy.min_fee = 1_000_000_000_000_000_000_000_000_000_000;
+    policy.max_fee = policy.min_fee;
+    client.set_fee_policy(&Some(policy));
+    assert_eq!(client.transfer_fee(&alice, &bob, &10_000), 500);
+    
+    client.mint(&admin, &alice, &10_000);
+    client.transfer(&alice, &bob, &10_000);
+    assert_eq!(client.balance(&bob), 9_500);
+    assert_eq!(client.balance(&treasury), 500);
+}
+
+/// batch_transfer cobra la comisión de cada partida
+#[test]
+fn test_batch_transfer_fee() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &10_000);
+    client.set_fee_policy(&Some(fee_policy(&treasury, Vec::new(&env))));
+    
+    client.delegate(&alice, &alice);
+    
+    client.batch_transfer(&alice, &vec![&env, (bob.clone(), 1000i128), (charlie.clone(), 3000i128)]);
+    
+    // El sender se debita una sola vez (un solo cambio de votos)
+    let alice_topics: Vec<Val> = (Symbol::new(&env, "delegate_votes_changed_event"), alice.clone()).into_val(&env);
+    let alice_updates = env.events().all().iter()
+        .filter(|(_, topics, _)| *topics == alice_topics)
+        .count();
+    assert_eq!(alice_updates, 1);
+    
+    assert_eq!(client.balance(&alice), 6000);
+    assert_eq!(client.balance(&bob), 990);
+    assert_eq!(client.balance(&charlie), 2970);
+    assert_eq!(client.balance(&treasury), 40);
+    assert_eq!(client.get_votes(&alice), 6000);
+}
+
+// ============================================================================
+// TESTS DE ALLOWLIST (TOKEN PERMISIONADO)
+// ============================================================================
+
+/// En modo allowlist solo cuentas aprobadas reciben tokens
+#[test]
+fn test_allowlist_blocks_unlisted_recipients() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig { allowlist_enabled: true, ..Default::default() }
+    );
+    assert!(client.allowlist_enabled());
+    
+    env.mock_all_auths();
+    assert_eq!(client.try_mint(&admin, &alice, &1000), Err(Ok(TokenError::NotAllowlisted)));
+    
+    client.set_allowlisted(&admin, &vec![&env, alice.clone(), bob.clone()], &true);
+    assert!(client.allowlisted(&alice));
+    assert!(!client.allowlisted(&charlie));
+    
+    client.mint(&admin, &alice, &1000);
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(
+        client.try_transfer(&alice, &charlie, &100),
+        Err(Ok(TokenError::NotAllowlisted.into()))
+    );
+    
+    client.approve(&alice, &bob, &100, &EXPIRATION_LEDGER);
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &charlie, &100),
+        Err(Ok(TokenError::NotAllowlisted.into()))
+    );
+    assert_eq!(
+        client.try_batch_transfer(&alice, &vec![&env, (bob.clone(), 10i128), (charlie.clone(), 10i128)]),
+        Err(Ok(TokenError::NotAllowlisted))
+    );
+    
+    assert_eq!(client.balance(&alice), 900);
+    assert_eq!(client.balance(&bob), 100);
+    assert_eq!(client.balance(&charlie), 0);
+}
+
+/// ComplianceManager administra la allowlist; una cuenta quitada puede salir
+#[test]
+fn test_allowlist_compliance_manager() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let manager = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig { allowlist_enabled: true, ..Default::default() }
+    );
+    
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_set_allowlisted(&manager, &vec![&env, alice.clone()], &true),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    client.grant_role(&admin, &manager, &Role::ComplianceManager);
+    client.set_allowlisted(&manager, &vec![&env, alice.clone(), bob.clone()], &true);
+    
+    client.set_allowlisted(&manager, &vec![&env, alice.clone()], &false);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "allowlist_updated_event"), alice.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [
+                        (Symbol::new(&env, "allowed"), false.into_val(&env)),
+                        (Symbol::new(&env, "caller"), manager.into_val(&env)),
+                    ]
+                ).into_val(&env),
+            ),
+        ]
+    );
+    assert!(!client.allowlisted(&alice));
+    
+    // alice ya no recibe, pero puede enviar lo que tiene
+    client.mint(&admin, &bob, &500);
+    assert_eq!(
+        client.try_transfer(&bob, &alice, &100),
+        Err(Ok(TokenError::NotAllowlisted.into()))
+    );
+    client.set_allowlisted(&manager, &vec![&env, alice.clone()], &true);
+    client.transfer(&bob, &alice, &100);
+    client.set_allowlisted(&manager, &vec![&env, alice.clone()], &false);
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(client.balance(&bob), 500);
+}
+
+/// Sin modo allowlist cualquier cuenta recibe
+#[test]
+fn test_allowlist_disabled_by_default() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    assert!(!client.allowlist_enabled());
+    client.mint(&admin, &alice, &1000);
+    assert_eq!(client.balance(&alice), 1000);
+}
+
+// ============================================================================
+// TESTS DE DENYLIST (SANCIONES)
+// ============================================================================
+
+/// Una cuenta sancionada no envía, recibe, quema, aprueba ni gasta
+#[test]
+fn test_denylist_blocks_account() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let mallory = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.mint(&admin, &mallory, &1000);
+    client.approve(&alice, &mallory, &500, &EXPIRATION_LEDGER);
+    
+    client.set_denylisted(&admin, &vec![&env, mallory.clone()], &true);
+    assert!(client.denylisted(&mallory));
+    
+    // Como sender y como destinatario
+    assert_eq!(client.try_transfer(&mallory, &bob, &100), Err(Ok(TokenError::AccountDenylisted.into())));
+    assert_eq!(client.try_transfer(&alice, &mallory, &100), Err(Ok(TokenError::AccountDenylisted.into())));
+    assert_eq!(client.try_burn(&mallory, &100), Err(Ok(TokenError::AccountDenylisted.into())));
+    assert_eq!(client.try_mint(&admin, &mallory, &100), Err(Ok(TokenError::AccountDenylisted)));
+    
+    // Como spender y como destino de un approval
+    assert_eq!(client.try_transfer_from(&mallory, &alice, &bob, &100), Err(Ok(TokenError::AccountDenylisted.into())));
+    assert_eq!(client.try_burn_from(&mallory, &alice, &100), Err(Ok(TokenError::AccountDenylisted.into())));
+    assert_eq!(client.try_approve(&alice, &mallory, &100, &EXPIRATION_LEDGER), Err(Ok(TokenError::AccountDenylisted.into())));
+    assert_eq!(client.try_approve(&mallory, &bob, &100, &EXPIRATION_LEDGER), Err(Ok(TokenError::AccountDenylisted.into())));
+    
+    // Revocar un approval siempre está permitido
+    client.approve(&alice, &mallory, &0, &0);
+    assert_eq!(client.allowance(&alice, &mallory), 0);
+    
+    // El resto del token sigue funcionando
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(client.balance(&bob), 100);
+    assert_eq!(client.balance(&mallory), 1000);
+    
+    // Quitar de la denylist restaura la cuenta
+    client.set_denylisted(&admin, &vec![&env, mallory.clone()], &false);
+    client.transfer(&mallory, &bob, &100);
+    assert_eq!(client.balance(&bob), 200);
+}
+
+/// Solo SanctionsManager administra la denylist, con un evento por cuenta
+#[test]
+fn test_denylist_sanctions_manager() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let officer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    let accounts = vec![&env, alice.clone(), bob.clone()];
+    assert_eq!(
+        client.try_set_denylisted(&officer, &accounts, &true),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    client.grant_role(&admin, &officer, &Role::SanctionsManager);
+    client.set_denylisted(&officer, &accounts, &true);
+    let data = Map::<Symbol, Val>::from_array(
+        &env,
+        [
+            (Symbol::new(&env, "caller"), officer.into_val(&env)),
+            (Symbol::new(&env, "denied"), true.into_val(&env)),
+        ]
+    );
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "denylist_updated_event"), alice.clone()).into_val(&env),
+                data.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "denylist_updated_event"), bob.clone()).into_val(&env),
+                data.into_val(&env),
+            ),
+        ]
+    );
+    assert!(client.denylisted(&alice));
+    assert!(client.denylisted(&bob));
+    assert_eq!(
+        client.try_batch_transfer(&admin, &vec![&env, (alice.clone(), 10i128)]),
+        Err(Ok(TokenError::AccountDenylisted))
+    );
+}
+
+// ============================================================================
+// TESTS DE UPGRADE
+// ============================================================================
+
+/// Primera versión publicada con upgrade() (esquema de storage 1),
+/// la que se actualiza en los tests (ver test_wasms/README.md)
+///
+/// Su initialize() todavía recibe las opciones sueltas (antes de TokenConfig)
+#[allow(clippy::too_many_arguments)]
+mod token_v1 {
+    soroban_sdk::contractimport!(file = "test_wasms/token_bdb_v1.wasm");
+}
+
+/// Build del código actual: la versión a la que se actualiza
+const UPGRADE_WASM: &[u8] = include_bytes!("../test_wasms/token_bdb.wasm");
+
+/// upgrade() de la primera versión a la actual conserva el storage
+#[test]
+fn test_upgrade_preserves_storage() {
+    let env = Env::default();
+    let contract_id = env.register(token_v1::WASM, ());
+    let v1 = token_v1::Client::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    v1.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &None,
+        &false,
+        &false
+    );
+    
+    env.mock_all_auths();
+    v1.mint(&admin, &alice, &1000);
+    v1.approve(&alice, &bob, &500, &EXPIRATION_LEDGER);
+    assert_eq!(v1.version(), 1);
+    
+    let wasm_hash = env.deployer().upload_contract_wasm(UPGRADE_WASM);
+    v1.upgrade(&wasm_hash);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "upgrade_event"), admin.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [
+                        (Symbol::new(&env, "new_wasm_hash"), wasm_hash.into_val(&env)),
+                        (Symbol::new(&env, "version"), 2u32.into_val(&env)),
+                    ]
+                ).into_val(&env),
+            ),
+        ]
+    );
+    
+    // El contrato ahora ejecuta la versión actual, con los mismos
+    // balances y supply y la versión incrementada
+    let client = TokenBDBClient::new(&env, &contract_id);
+    assert_eq!(client.version(), 2);
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.total_supply(), 1000);
+    assert_eq!(client.allowance(&alice, &bob), 500);
+    assert_eq!(client.timelock_delay(), 0);
+    
+    // El esquema de la primera versión se migra antes de operar
+    assert_eq!(client.storage_version(), 1);
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &400),
+        Err(Ok(TokenError::SchemaOutdated.into()))
+    );
+    client.migrate(&vec![&env, (alice.clone(), bob.clone())], &true);
+    assert_eq!(client.storage_version(), 2);
+    
+    client.transfer(&alice, &bob, &400);
+    client.transfer_from(&bob, &alice, &bob, &100);
+    assert_eq!(client.balance(&alice), 500);
+    assert_eq!(client.balance(&bob), 500);
+    assert_eq!(client.allowance(&alice, &bob), 400);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Solo el admin puede hacer upgrade
+#[test]
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    let wasm_hash = env.deployer().upload_contract_wasm(UPGRADE_WASM);
+    env.mock_auths(&[MockAuth {
+        address: &attacker,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "upgrade",
+            args: (wasm_hash.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_upgrade(&wasm_hash).is_err());
+    assert_eq!(client.version(), 1);
+}
+
+// ============================================================================
+// TESTS DE MIGRACIÓN DE STORAGE
+// ============================================================================
+
+/// Simula un contrato desplegado con el esquema 1 (allowances i128)
+fn downgrade_to_schema_v1(env: &Env, contract_id: &Address, from: &Address, spender: &Address, amount: i128) {
+    env.as_contract(contract_id, || {
+        env.storage().instance().remove(&DataKey::StorageVersion);
+        env.storage().persistent().set(
+            &DataKey::Allowance(from.clone(), spender.clone()),
+            &amount
+        );
+    });
+}
+
+/// Con el esquema desactualizado las operaciones se niegan hasta migrar
+#[test]
+fn test_outdated_schema_blocks_operations() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    assert_eq!(client.storage_version(), 2);
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    downgrade_to_schema_v1(&env, &contract_id, &alice, &bob, 500);
+    assert_eq!(client.storage_version(), 1);
+    
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &100),
+        Err(Ok(TokenError::SchemaOutdated.into()))
+    );
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &bob, &100),
+        Err(Ok(TokenError::SchemaOutdated.into()))
+    );
+    assert_eq!(client.try_mint(&admin, &alice, &100), Err(Ok(TokenError::SchemaOutdated)));
+    assert_eq!(client.try_set_max_supply(&10_000), Err(Ok(TokenError::SchemaOutdated)));
+    
+    // Las consultas siguen funcionando y entienden el formato anterior
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.allowance(&alice, &bob), 500);
+}
+
+/// migrate() reescribe los allowances por lotes y marca el esquema actual
+#[test]
+fn test_migrate_allowances() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    downgrade_to_schema_v1(&env, &contract_id, &alice, &bob, 500);
+    
+    // Primer lote sin completar: el esquema sigue desactualizado
+    client.migrate(&vec![&env, (alice.clone(), bob.clone())], &false);
+    assert_eq!(client.storage_version(), 1);
+    env.as_contract(&contract_id, || {
+        let value: AllowanceValue = env.storage().persistent()
+            .get(&DataKey::Allowance(alice.clone(), bob.clone()))
+            .unwrap();
+        assert_eq!(value.amount, 500);
+        assert_eq!(value.expiration_ledger, env.ledger().max_live_until_ledger());
+    });
+    
+    client.migrate(&vec![&env], &true);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "migrated_event"), admin.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [(Symbol::new(&env, "storage_version"), 2u32.into_val(&env))]
+                ).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.storage_version(), 2);
+    assert_eq!(client.try_migrate(&vec![&env], &true), Err(Ok(TokenError::AlreadyMigrated)));
+    
+    // El allowance migrado se puede gastar
+    client.transfer_from(&bob, &alice, &charlie, &300);
+    assert_eq!(client.balance(&charlie), 300);
+    assert_eq!(client.allowance(&alice, &bob), 200);
+}
+
+// ============================================================================
+// TESTS DE TIMELOCK
+// ============================================================================
+
+/// Con delay, las operaciones sensibles solo pasan por schedule()/execute()
+#[test]
+fn test_timelock_schedule_execute() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    env.ledger().set_sequence_number(10);
+    
+    assert_eq!(client.timelock_delay(), 0);
+    assert_eq!(client.try_set_timelock_delay(&535_681), Err(Ok(TokenError::InvalidAmount)));
+    client.set_timelock_delay(&100);
+    assert_eq!(client.timelock_delay(), 100);
+    
+    // Las llamadas directas quedan bloqueadas
+    assert_eq!(client.try_set_max_supply(&5000), Err(Ok(TokenError::TimelockRequired)));
+    assert_eq!(client.try_propose_admin(&alice), Err(Ok(TokenError::TimelockRequired)));
+    assert_eq!(client.try_set_fee_policy(&None), Err(Ok(TokenError::TimelockRequired)));
+    assert_eq!(client.try_set_timelock_delay(&0), Err(Ok(TokenError::TimelockRequired)));
+    
+    // El eta tiene que respetar el delay completo
+    let op = AdminOp::SetMaxSupply(5000);
+    assert_eq!(client.try_schedule(&vec![&env], &op, &109), Err(Ok(TokenError::TimelockTooEarly)));
+    
+    let op_id = client.schedule(&vec![&env], &op, &110);
+    assert_eq!(op_id, 0);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "operation_scheduled_event"), 0u32).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [
+                        (Symbol::new(&env, "eta_ledger"), 110u32.into_val(&env)),
+                        (Symbol::new(&env, "op"), op.clone().into_val(&env)),
+                    ]
+                ).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.scheduled_op(&op_id), Some(ScheduledOp { op, eta_ledger: 110 }));
+    
+    env.ledger().set_sequence_number(109);
+    assert_eq!(client.try_execute(&vec![&env], &op_id), Err(Ok(TokenError::TimelockNotReady)));
+    
+    env.ledger().set_sequence_number(110);
+    client.execute(&vec![&env], &op_id);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "operation_executed_event"), 0u32).into_val(&env),
+                Map::<Symbol, Val>::new(&env).into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "max_supply_set_event"), admin.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [(Symbol::new(&env, "max_supply"), 5000i128.into_val(&env))]
+                ).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.max_supply(), Some(5000));
+    
+    // Cada operación se ejecuta una sola vez
+    assert_eq!(client.scheduled_op(&op_id), None);
+    assert_eq!(client.try_execute(&vec![&env], &op_id), Err(Ok(TokenError::OperationNotFound)));
+    
+    // El propio delay también pasa por el timelock
+    let op_id = client.schedule(&vec![&env], &AdminOp::SetTimelockDelay(0), &210);
+    env.ledger().set_sequence_number(210);
+    client.execute(&vec![&env], &op_id);
+    assert_eq!(client.timelock_delay(), 0);
+    client.set_max_supply(&4000);
+    assert_eq!(client.max_supply(), Some(4000));
+}
+
+/// cancel() descarta la operación; pause() sigue siendo inmediato
+#[test]
+fn test_timelock_cancel_and_unpause() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.set_timelock_delay(&50);
+    
+    // Cancelar un cambio de admin programado
+    let op_id = client.schedule(&vec![&env], &AdminOp::ProposeAdmin(alice.clone()), &50);
+    client.cancel(&vec![&env], &op_id);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "operation_cancelled_event"), op_id).into_val(&env),
+                Map::<Symbol, Val>::new(&env).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.scheduled_op(&op_id), None);
+    env.ledger().set_sequence_number(50);
+    assert_eq!(client.try_execute(&vec![&env], &op_id), Err(Ok(TokenError::OperationNotFound)));
+    assert_eq!(client.try_cancel(&vec![&env], &op_id), Err(Ok(TokenError::OperationNotFound)));
+    assert_eq!(client.pending_admin(), None);
+    
+    // pause() no espera; unpause() sí
+    client.pause(&admin, &PauseScope::All);
+    assert_eq!(
+        client.try_unpause(&admin, &PauseScope::All),
+        Err(Ok(TokenError::TimelockRequired))
+    );
+    
+    let op_id = client.schedule(&vec![&env], &AdminOp::Unpause(PauseScope::All), &100);
+    assert_eq!(op_id, 1);
+    env.ledger().set_sequence_number(100);
+    client.execute(&vec![&env], &op_id);
+    assert!(!client.paused());
+}
+
+/// Con el delay máximo, la operación sigue en storage hasta su eta
+#[test]
+fn test_timelock_long_delay_ttl() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let proposed = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    // Activar el timelock descarta una propuesta hecha sin espera
+    client.propose_admin(&proposed);
+    client.set_timelock_delay(&500_000);
+    assert_eq!(client.pending_admin(), None);
+    assert_eq!(client.try_accept_admin(), Err(Ok(TokenError::NoPendingAdmin)));
+    
+    // El TTL cubre el delay más la ventana de ejecución
+    let op_id = client.schedule(&vec![&env], &AdminOp::SetMaxSupply(5000), &500_000);
+    env.as_contract(&contract_id, || {
+        let ttl = env.storage().persistent().get_ttl(&DataKey::TimelockOp(op_id));
+        assert_eq!(ttl, 600_000);
+    });
+    
+    // Un eta que ninguna entrada puede alcanzar se rechaza
+    let unreachable = env.ledger().max_live_until_ledger();
+    assert_eq!(
+        client.try_schedule(&vec![&env], &AdminOp::SetMaxSupply(5000), &unreachable),
+        Err(Ok(TokenError::InvalidExpiration))
+    );
+    
+    env.ledger().set_sequence_number(500_000);
+    client.execute(&vec![&env], &op_id);
+    assert_eq!(client.max_supply(), Some(5000));
+}
+
+// ============================================================================
+// TESTS DE ADMIN MULTISIG
+// ============================================================================
+
+/// Un admin 3-of-5 solo mintea con tres firmantes distintos
+#[test]
+fn test_multisig_admin_mint() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let signers = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    let set = SignerSet { signers: signers.clone(), threshold: 3 };
+    client.set_admin_signers(&vec![&env], &set);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "admin_signers_set_event"), admin.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [
+                        (Symbol::new(&env, "signers"), signers.clone().into_val(&env)),
+                        (Symbol::new(&env, "threshold"), 3u32.into_val(&env)),
+                    ]
+                ).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.admin(), contract_id);
+    assert_eq!(client.admin_signers(), Some(set));
+    
+    // Ni el admin anterior ni las llamadas directas de admin sirven
+    assert_eq!(client.try_mint(&admin, &alice, &1000), Err(Ok(TokenError::Unauthorized)));
+    assert_eq!(client.try_mint(&contract_id, &alice, &1000), Err(Ok(TokenError::ThresholdNotMet)));
+    assert_eq!(client.try_set_max_supply(&5000), Err(Ok(TokenError::ThresholdNotMet)));
+    
+    // Aprobaciones insuficientes, ajenas o repetidas
+    let op = AdminOp::Mint(alice.clone(), 1000);
+    let two = vec![&env, signers.get(0).unwrap(), signers.get(1).unwrap()];
+    assert_eq!(client.try_multisig_execute(&two, &op), Err(Ok(TokenError::ThresholdNotMet)));
+    let mut with_outsider = two.clone();
+    with_outsider.push_back(outsider);
+    assert_eq!(client.try_multisig_execute(&with_outsider, &op), Err(Ok(TokenError::Unauthorized)));
+    let mut repeated = two.clone();
+    repeated.push_back(signers.get(0).unwrap());
+    assert_eq!(client.try_multisig_execute(&repeated, &op), Err(Ok(TokenError::Unauthorized)));
+    
+    // Tres firmantes: cada uno autoriza la llamada
+    let mut three = two.clone();
+    three.push_back(signers.get(4).unwrap());
+    client.multisig_execute(&three, &op);
+    let auths = env.auths();
+    assert_eq!(auths.len(), 3);
+    for (index, (address, _)) in auths.iter().enumerate() {
+        assert_eq!(*address, three.get(index as u32).unwrap());
+    }
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// El conjunto de firmantes se cambia con el umbral vigente
+#[test]
+fn test_multisig_signer_rotation() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let pauser = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let (a, b, c) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    
+    // Umbral inalcanzable, nulo o firmantes repetidos
+    for set in [
+        SignerSet { signers: vec![&env, a.clone(), b.clone()], threshold: 3 },
+        SignerSet { signers: vec![&env, a.clone(), b.clone()], threshold: 0 },
+        SignerSet { signers: vec![&env, a.clone(), a.clone()], threshold: 2 },
+    ] {
+        assert_eq!(
+            client.try_set_admin_signers(&vec![&env], &set),
+            Err(Ok(TokenError::InvalidSignerSet))
+        );
+    }
+    
+    client.set_admin_signers(&vec![&env], &SignerSet {
+        signers: vec![&env, a.clone(), b.clone()],
+        threshold: 2,
+    });
+    
+    // Rotar a {b, c} con la aprobación de {a, b}
+    let rotated = SignerSet { signers: vec![&env, b.clone(), c.clone()], threshold: 2 };
+    assert_eq!(
+        client.try_set_admin_signers(&vec![&env, a.clone()], &rotated),
+        Err(Ok(TokenError::ThresholdNotMet))
+    );
+    client.set_admin_signers(&vec![&env, a.clone(), b.clone()], &rotated);
+    assert_eq!(
+        client.try_multisig_execute(&vec![&env, a.clone(), b.clone()], &AdminOp::SetMaxSupply(5000)),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    // Delegar un rol operativo
+    client.multisig_execute(&vec![&env, b.clone(), c.clone()], &AdminOp::GrantRole(pauser.clone(), Role::Pauser));
+    client.pause(&pauser, &PauseScope::All);
+    assert!(client.paused());
+    
+    // Volver a un admin de una sola cuenta
+    client.multisig_execute(&vec![&env, b.clone(), c.clone()], &AdminOp::ProposeAdmin(new_admin.clone()));
+    client.accept_admin();
+    assert_eq!(client.admin(), new_admin);
+    assert_eq!(client.admin_signers(), None);
+    client.set_max_supply(&5000);
+    assert_eq!(client.max_supply(), Some(5000));
+}
+
+/// Una propuesta de admin anterior al multisig queda descartada
+#[test]
+fn test_multisig_discards_pending_admin() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let proposed = Address::generate(&env);
+    let (a, b, c) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.propose_admin(&proposed);
+    client.set_admin_signers(&vec![&env], &SignerSet {
+        signers: vec![&env, a, b, c],
+        threshold: 2,
+    });
+    
+    assert_eq!(client.pending_admin(), None);
+    assert_eq!(client.try_accept_admin(), Err(Ok(TokenError::NoPendingAdmin)));
+    assert_eq!(client.admin(), contract_id);
+    assert!(client.admin_signers().is_some());
+}
+
+/// Un admin multisig administra cupos, congelamientos, lotes y renuncia
+#[test]
+fn test_multisig_admin_operations() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let (a, b, c) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.set_admin_signers(&vec![&env], &SignerSet {
+        signers: vec![&env, a.clone(), b.clone(), c],
+        threshold: 2,
+    });
+    let approvers = vec![&env, a, b];
+    
+    // Las funciones de admin directas no están disponibles...
+    assert_eq!(
+        client.try_set_minter_quota(&minter, &500, &0, &0),
+        Err(Ok(TokenError::ThresholdNotMet))
+    );
+    assert_eq!(client.try_set_authorized(&alice, &false), Err(Ok(TokenError::ThresholdNotMet)));
+    assert_eq!(client.try_renounce_admin(), Err(Ok(TokenError::ThresholdNotMet)));
+    
+    // ...pero sí vía multisig_execute()
+    client.multisig_execute(&approvers, &AdminOp::SetMinterQuota(minter.clone(), 500, 0, 0));
+    client.multisig_execute(&approvers, &AdminOp::GrantRole(minter.clone(), Role::Minter));
+    client.mint(&minter, &alice, &500);
+    assert_eq!(client.try_mint(&minter, &alice, &1), Err(Ok(TokenError::MintQuotaExceeded)));
+    
+    client.multisig_execute(&approvers, &AdminOp::SetAuthorized(alice.clone(), false));
+    assert!(!client.authorized(&alice));
+    client.multisig_execute(&approvers, &AdminOp::SetAuthorized(alice.clone(), true));
+    assert!(client.authorized(&alice));
+    
+    client.multisig_execute(&approvers, &AdminOp::SetMaxBatchSize(5));
+    assert_eq!(client.max_batch_size(), 5);
+    
+    client.multisig_execute(&approvers, &AdminOp::RenounceAdmin);
+    assert_eq!(client.admin_signers(), None);
+    assert_eq!(client.try_mint(&minter, &alice, &1), Err(Ok(TokenError::AdminRenounced)));
+}
+
+/// Airdrops y vesting con un admin multisig: los sobrantes van a `to`
+#[test]
+fn test_multisig_airdrop_and_vesting() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let (a, b, c) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    client.set_admin_signers(&vec![&env], &SignerSet {
+        signers: vec![&env, a.clone(), b.clone(), c],
+        threshold: 2,
+    });
+    let approvers = vec![&env, a, b];
+    
+    // Airdrop: la reserva no reclamada va al tesoro, no al contrato
+    let root = BytesN::from_array(&env, &[7u8; 32]);
+    client.multisig_execute(&approvers, &AdminOp::StartAirdrop(root, 1000, 100));
+    assert_eq!(client.balance(&contract_id), 1000);
+    env.ledger().set_sequence_number(101);
+    assert_eq!(
+        client.try_multisig_execute(&approvers, &AdminOp::SweepAirdrop(contract_id.clone())),
+        Err(Ok(TokenError::InvalidRecipient))
+    );
+    client.multisig_execute(&approvers, &AdminOp::SweepAirdrop(treasury.clone()));
+    assert_eq!(client.balance(&treasury), 1000);
+    assert_eq!(client.airdrop(), None);
+    
+    // Vesting: la parte no liberada vuelve al tesoro
+    client.multisig_execute(&approvers, &AdminOp::CreateVesting(alice.clone(), 1000, 200, 200, 1200, true));
+    assert_eq!(client.vesting(&0).unwrap().beneficiary, alice);
+    env.ledger().set_sequence_number(450);
+    client.multisig_execute(&approvers, &AdminOp::RevokeVesting(0, treasury.clone()));
+    assert_eq!(client.balance(&treasury), 1750);
+    assert_eq!(client.release(&0), 250);
+    assert_eq!(client.balance(&contract_id), 0);
+}
+
+// ============================================================================
+// TESTS DE METADATA
+// ============================================================================
+
+/// set_metadata() cambia nombre y símbolo; los decimales no cambian
+#[test]
+fn test_set_metadata() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    let rebrand = TokenMetadata {
+        name: String::from_str(&env, "Buen Día Builders Token"),
+        symbol: String::from_str(&env, "BDB"),
+        decimals: 7,
+    };
+    client.set_metadata(&admin, &rebrand);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "metadata_updated_event"), admin.clone()).into_val(&env),
+                Map::<Symbol, Val>::from_array(
+                    &env,
+                    [
+                        (Symbol::new(&env, "name"), rebrand.name.clone().into_val(&env)),
+                        (Symbol::new(&env, "symbol"), rebrand.symbol.clone().into_val(&env)),
+                    ]
+                ).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.metadata(), rebrand);
+    assert_eq!(client.name(), rebrand.name);
+    assert_eq!(client.symbol(), rebrand.symbol);
+    
+    // Decimales inmutables y mismas reglas que initialize()
+    let mut invalid = rebrand.clone();
+    invalid.decimals = 6;
+    assert_eq!(client.try_set_metadata(&admin, &invalid), Err(Ok(TokenError::InvalidDecimals)));
+    let mut invalid = rebrand.clone();
+    invalid.symbol = String::from_str(&env, "");
+    assert_eq!(client.try_set_metadata(&admin, &invalid), Err(Ok(TokenError::InvalidMetadata)));
+    let mut invalid = rebrand.clone();
+    invalid.name = String::from_str(&env, &"N".repeat(101));
+    assert_eq!(client.try_set_metadata(&admin, &invalid), Err(Ok(TokenError::InvalidMetadata)));
+    assert_eq!(client.metadata(), rebrand);
+}
+
+/// Solo el admin o una cuenta con MetadataManager actualiza la metadata
+#[test]
+fn test_set_metadata_requires_role() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let manager = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &TokenConfig::default()
+    );
+    
+    env.mock_all_auths();
+    let metadata = TokenMetadata {
+        name: String::from_str(&env, "Nuevo Token"),
+        symbol: String::from_str(&env, "NEW"),
+        decimals: 7,
+    };
+    assert_eq!(client.try_set_metadata(&manager, &metadata), Err(Ok(TokenError::Unauthorized)));
+    
+    client.grant_role(&admin, &manager, &Role::MetadataManager);
+    client.set_metadata(&manager, &metadata);
+    assert_eq!(client.metadata(), metadata);
+}
@@ -0,0 +1,19 @@
+use crate::storage::VestingSchedule;
+
+/// Monto liberado por el calendario en `ledger`
+///
+/// 0 antes del cliff, `total` desde end_ledger y lineal entre
+/// start_ledger y end_ledger (el cliff solo retrasa el primer pago).
+/// None si el cálculo desborda
+pub fn vested_amount(schedule: &VestingSchedule, ledger: u32) -> Option<i128> {
+    if ledger < schedule.cliff_ledger {
+        return Some(0);
+    }
+    if ledger >= schedule.end_ledger {
+        return Some(schedule.total);
+    }
+
+    let elapsed = (ledger - schedule.start_ledger) as i128;
+    let duration = (schedule.end_ledger - schedule.start_ledger) as i128;
+    schedule.total.checked_mul(elapsed).map(|vested| vested / duration)
+}